@@ -12,7 +12,7 @@ fn generate_known_image() -> BufferedImage<Rgba<u8>> {
     BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]))
 }
 
-fn main() -> () {
+fn main() {
     // Generate a image we can paste to our canvas. In a real application, this may be an opened
     // image file or buffer of some sort. For the sake of example, the constants IMAGE_WIDTH and IMAGE_HEIGHT
     // will represent our known image dimensions.
@@ -34,7 +34,6 @@ fn main() -> () {
     let images: Vec<&BufferedImage<Rgba<u8>>> = vec![&known_image; TOTAL_IMAGES as usize];
     merger.bulk_push(&images);
 
-    // Finally, we can get the canvas and save it - we should have a red image with 10000 pixels.
-    let canvas = merger.get_canvas();
-    canvas.save("examples/known_size_merger.png").unwrap();
+    // Finally, we can save the canvas - we should have a red image with 10000 pixels.
+    merger.save("examples/known_size_merger.png").unwrap();
 }
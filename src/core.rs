@@ -1,7 +1,12 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    ops::{Deref, DerefMut},
+    path::Path,
+};
 
 use image::{ImageBuffer, ImageFormat, Luma, LumaA, Pixel, Rgb, Rgba};
 
+use crate::MergeError;
+
 /// Represents an image that can be passed to the merger. This is a wrapper around an image crate's GenericImage
 /// and adds some additional functionality for the merger.
 /// # Type Parameters
@@ -24,7 +29,7 @@ pub struct Image<P: Pixel, U: image::GenericImage<Pixel = P>> {
 impl<P: Pixel, U: image::GenericImage<Pixel = P>> Image<P, U> {
     /// Returns the capacity of the underlying image's data buffer.
     pub fn capacity(&self) -> usize {
-        return self.underlying.pixels().count() * <P as Pixel>::CHANNEL_COUNT as usize;
+        self.underlying.pixels().count() * <P as Pixel>::CHANNEL_COUNT as usize
     }
 
     /// Consumes the image and returns the underlying image buffer.
@@ -51,6 +56,44 @@ where
     pub fn new_from_raw(width: u32, height: u32, container: Container) -> Option<Self> {
         ImageBuffer::from_raw(width, height, container).map(|image| Self { underlying: image })
     }
+
+    /// Creates a new image from a raw buffer, like `new_from_raw`, but the buffer must hold exactly as many subpixels as the
+    /// image needs, and a mismatch is reported with both lengths so it can be diagnosed.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the image.
+    /// * `height` - The height of the image.
+    /// * `container` - The raw buffer to create the image from.
+    ///
+    /// # Returns
+    /// * `Ok` - An [Image](Image) with the given pixel and buffer type.
+    /// * `Err` - A [MergeError::BufferSizeMismatch](MergeError::BufferSizeMismatch) if the buffer is not the right length.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{BufferedImage, MergeError, Rgb};
+    ///
+    /// assert!(BufferedImage::<Rgb<u8>>::try_from_raw(2, 2, vec![0; 12]).is_ok());
+    /// assert!(matches!(
+    ///     BufferedImage::<Rgb<u8>>::try_from_raw(2, 2, vec![0; 13]),
+    ///     Err(MergeError::BufferSizeMismatch { expected: 12, got: 13 })
+    /// ));
+    /// ```
+    pub fn try_from_raw(width: u32, height: u32, container: Container) -> Result<Self, MergeError> {
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(<P as Pixel>::CHANNEL_COUNT as usize))
+            .unwrap_or(usize::MAX);
+        if container.len() != expected {
+            return Err(MergeError::BufferSizeMismatch {
+                expected,
+                got: container.len(),
+            });
+        }
+
+        // Can always unwrap here because the length of the buffer was checked above.
+        Ok(Self::new_from_raw(width, height, container).unwrap())
+    }
 }
 
 impl<P: Pixel> Image<P, ImageBuffer<P, Vec<P::Subpixel>>> {
@@ -151,7 +194,42 @@ where
     fn from_with_format(container: Container, format: ImageFormat) -> Self;
 }
 
-macro_rules! impl_from_with_format {
+/// A trait that allows the creation of an Image from the encoded bytes of an image file, such as a downloaded PNG.
+pub trait FromBytes: Sized {
+    /// Decodes the given bytes into an Image. The image format is derived from the contents of the bytes.
+    /// # Arguments
+    /// * `bytes` - The encoded image.
+    /// # Returns
+    /// An [Image](Image) with the given pixel and buffer type, or an error if the format could not be recognized or the bytes
+    /// could not be decoded.
+    /// # Example
+    /// ```no_run
+    /// use image_merger::{FromBytes, Rgba, BufferedImage};
+    ///
+    /// let bytes = std::fs::read("image.png").unwrap();
+    /// let image: BufferedImage<Rgba<u8>> = BufferedImage::from_bytes(&bytes).expect("Could not decode image!");
+    /// ```
+    fn from_bytes(bytes: &[u8]) -> image::ImageResult<Self>;
+}
+
+/// A trait that allows the creation of an Image by opening an image file from disk.
+pub trait FromPath: Sized {
+    /// Opens the image file at the given path and transforms it into an Image. The image format is derived from the file's
+    /// contents and extension.
+    /// # Arguments
+    /// * `path` - The path of the image file to open.
+    /// # Returns
+    /// An [Image](Image) with the given pixel and buffer type, or an error if the file could not be read or decoded.
+    /// # Example
+    /// ```no_run
+    /// use image_merger::{FromPath, Rgba, BufferedImage};
+    ///
+    /// let image: BufferedImage<Rgba<u8>> = BufferedImage::from_path("image.png").expect("Could not open image!");
+    /// ```
+    fn from_path<Q: AsRef<Path>>(path: Q) -> image::ImageResult<Self>;
+}
+
+macro_rules! impl_loaders {
     ($px_type:ident, $channel_type:ty, $to_fn:ident) => {
         #[doc = concat!(
             r#"Implementation of [`FromWithFormat`](FromWithFormat) for an [`Image`](Image) with a pixel type of [`"#,
@@ -181,19 +259,73 @@ macro_rules! impl_from_with_format {
                 Self::from(img)
             }
         }
+
+        #[doc = concat!(
+            r#"Implementation of [`FromPath`](FromPath) for an [`Image`](Image) with a pixel type of [`"#,
+            stringify!($px_type),
+            "`](image::",
+            stringify!($px_type),
+            "), holding a subpixel type of [`",
+            stringify!($channel_type),
+            r#"`]("#
+            , stringify!($channel_type),
+            r#") and an underlying [`ImageBuffer`](image::ImageBuffer) buffer that holds `Vec<"#,
+            stringify!($channel_type),
+            r#">`'s.
+        "#)]
+        impl FromPath
+            for Image<
+                $px_type<$channel_type>,
+                ImageBuffer<$px_type<$channel_type>, Vec<$channel_type>>,
+            >
+        {
+            fn from_path<Q: AsRef<Path>>(path: Q) -> image::ImageResult<Self> {
+                let dyn_image = image::open(path)?;
+                let img = dyn_image.$to_fn();
+
+                Ok(Self::from(img))
+            }
+        }
+
+        #[doc = concat!(
+            r#"Implementation of [`FromBytes`](FromBytes) for an [`Image`](Image) with a pixel type of [`"#,
+            stringify!($px_type),
+            "`](image::",
+            stringify!($px_type),
+            "), holding a subpixel type of [`",
+            stringify!($channel_type),
+            r#"`]("#
+            , stringify!($channel_type),
+            r#") and an underlying [`ImageBuffer`](image::ImageBuffer) buffer that holds `Vec<"#,
+            stringify!($channel_type),
+            r#">`'s.
+        "#)]
+        impl FromBytes
+            for Image<
+                $px_type<$channel_type>,
+                ImageBuffer<$px_type<$channel_type>, Vec<$channel_type>>,
+            >
+        {
+            fn from_bytes(bytes: &[u8]) -> image::ImageResult<Self> {
+                let dyn_image = image::load_from_memory(bytes)?;
+                let img = dyn_image.$to_fn();
+
+                Ok(Self::from(img))
+            }
+        }
     };
 }
 
-impl_from_with_format!(Rgb, u8, into_rgb8);
-impl_from_with_format!(Rgb, u16, into_rgb16);
-impl_from_with_format!(Rgb, f32, into_rgb32f);
+impl_loaders!(Rgb, u8, into_rgb8);
+impl_loaders!(Rgb, u16, into_rgb16);
+impl_loaders!(Rgb, f32, into_rgb32f);
 
-impl_from_with_format!(Rgba, u8, into_rgba8);
-impl_from_with_format!(Rgba, u16, into_rgba16);
-impl_from_with_format!(Rgba, f32, into_rgba32f);
+impl_loaders!(Rgba, u8, into_rgba8);
+impl_loaders!(Rgba, u16, into_rgba16);
+impl_loaders!(Rgba, f32, into_rgba32f);
 
-impl_from_with_format!(Luma, u8, into_luma8);
-impl_from_with_format!(Luma, u16, into_luma16);
+impl_loaders!(Luma, u8, into_luma8);
+impl_loaders!(Luma, u16, into_luma16);
 
-impl_from_with_format!(LumaA, u8, into_luma_alpha8);
-impl_from_with_format!(LumaA, u16, into_luma_alpha16);
+impl_loaders!(LumaA, u8, into_luma_alpha8);
+impl_loaders!(LumaA, u16, into_luma_alpha16);
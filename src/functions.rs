@@ -1,18 +1,24 @@
-use crate::{cell::ImageCell, core::Image, merger::Point, BufferedImage};
-use image::Pixel;
-use rayon::{
-    iter::IntoParallelIterator,
-    prelude::{IndexedParallelIterator, ParallelIterator},
-    slice::ParallelSlice,
+use crate::{
+    cell::ImageCell,
+    core::Image,
+    merger::{BlendMode, Point},
+    parallel::prelude::*,
+    BufferedImage,
 };
+use image::{Pixel, Primitive};
+use num_traits::NumCast;
 use std::{marker::Sync, ops::DerefMut};
 
 /// The library's underlying paste method. This is only used internally and should not be used by the user, but is exposed
-/// through the raw module for documentation purposes.
+/// through the raw module for documentation purposes. Pixels are copied onto the bottom image as-is, without any alpha blending,
+/// so pasting is equally fast for opaque and transparent images.
 /// # Arguments
 /// * `bottom` - The image to paste onto.
 /// * `top` - The image to paste.
 /// * `loc` - The location to paste the top image at.
+///
+/// # Panics
+/// In debug builds, this function will panic if the top image does not fit completely on the bottom image at the given location.
 pub fn paste<P, Container>(
     bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
     top: &Image<P, image::ImageBuffer<P, Container>>,
@@ -22,6 +28,18 @@ pub fn paste<P, Container>(
     <P as Pixel>::Subpixel: Sync,
     Container: DerefMut<Target = [P::Subpixel]>,
 {
+    debug_assert!(
+        loc.x as u64 + top.width() as u64 <= bottom.width() as u64
+            && loc.y as u64 + top.height() as u64 <= bottom.height() as u64,
+        "A {}x{} image pasted at ({}, {}) does not fit on a {}x{} canvas.",
+        top.width(),
+        top.height(),
+        loc.x,
+        loc.y,
+        bottom.width(),
+        bottom.height()
+    );
+
     // Go through each pixel in the image (at once), grab its relative location on the canvas,
     // and alter the canvas underlying buffer to reflect the new pixel.
     let image_width = top.width();
@@ -42,6 +60,118 @@ pub fn paste<P, Container>(
         });
 }
 
+/// The library's underlying blend method. This is only used internally and should not be used by the user, but is exposed
+/// through the raw module for documentation purposes. Blends the top pixel into the bottom pixel with the given mode. Integer
+/// subpixels saturate at their maximum value, floating point subpixels are not clamped, so values above 1.0 are kept.
+/// # Arguments
+/// * `mode` - How the pixels are combined.
+/// * `bottom` - The pixel on the canvas, which is overwritten with the result.
+/// * `top` - The pixel being pasted.
+pub fn blend_pixel<P: Pixel>(mode: BlendMode, bottom: &mut P, top: &P) {
+    let blend: fn(f32, f32) -> f32 = match mode {
+        BlendMode::Over => return bottom.blend(top),
+        BlendMode::Add => |bottom, top| bottom + top,
+        BlendMode::Multiply => |bottom, top| bottom * top,
+        BlendMode::Screen => |bottom, top| 1.0 - (1.0 - bottom) * (1.0 - top),
+        BlendMode::OverPremultiplied => return blend_premultiplied(bottom, top),
+    };
+
+    let has_alpha = P::COLOR_MODEL.ends_with('A');
+    let channels = bottom.channels_mut();
+    let color_channels = channels.len() - has_alpha as usize;
+    for (index, (bottom, top)) in channels.iter_mut().zip(top.channels()).enumerate() {
+        let (b, t) = (normalize(*bottom), normalize(*top));
+        *bottom = if index < color_channels {
+            denormalize(blend(b, t))
+        } else {
+            // The alpha channel is combined like source-over compositing does.
+            denormalize(t + b * (1.0 - t))
+        };
+    }
+}
+
+/// The library's underlying watermark blend method. This is only used internally and should not be used by the user, but is
+/// exposed through the raw module for documentation purposes. Composites the top pixel over the bottom pixel with its alpha
+/// scaled by the given opacity. Pixels without an alpha channel are treated as fully opaque.
+/// # Arguments
+/// * `bottom` - The pixel on the canvas, which is overwritten with the result.
+/// * `top` - The pixel being drawn over it.
+/// * `opacity` - How opaque the top pixel is drawn, from 0.0 (invisible) to 1.0. Values outside of this range are clamped.
+pub fn blend_pixel_with_opacity<P: Pixel>(bottom: &mut P, top: &P, opacity: f32) {
+    let has_alpha = P::COLOR_MODEL.ends_with('A');
+    let alpha_of = |pixel: &[P::Subpixel]| {
+        if has_alpha {
+            normalize(pixel[pixel.len() - 1])
+        } else {
+            1.0
+        }
+    };
+
+    let top_alpha = alpha_of(top.channels()) * opacity.clamp(0.0, 1.0);
+    let bottom_alpha = alpha_of(bottom.channels());
+    let alpha = top_alpha + bottom_alpha * (1.0 - top_alpha);
+
+    let channels = bottom.channels_mut();
+    let color_channels = channels.len() - has_alpha as usize;
+    for (bottom, top) in channels[..color_channels].iter_mut().zip(top.channels()) {
+        let (b, t) = (normalize(*bottom), normalize(*top));
+        let color = if alpha > 0.0 {
+            (t * top_alpha + b * bottom_alpha * (1.0 - top_alpha)) / alpha
+        } else {
+            0.0
+        };
+        *bottom = denormalize(color);
+    }
+    if has_alpha {
+        channels[color_channels] = denormalize(alpha);
+    }
+}
+
+/// Composites a top pixel with premultiplied alpha over a bottom pixel with straight alpha, keeping the result straight.
+fn blend_premultiplied<P: Pixel>(bottom: &mut P, top: &P) {
+    let has_alpha = P::COLOR_MODEL.ends_with('A');
+    if !has_alpha {
+        *bottom = *top;
+        return;
+    }
+
+    let channels = bottom.channels_mut();
+    let color_channels = channels.len() - 1;
+    let top_alpha = normalize(top.channels()[color_channels]);
+    let bottom_alpha = normalize(channels[color_channels]);
+    let alpha = top_alpha + bottom_alpha * (1.0 - top_alpha);
+
+    for (bottom, top) in channels[..color_channels].iter_mut().zip(top.channels()) {
+        let (b, t) = (normalize(*bottom), normalize(*top));
+        // The top color already carries its alpha, only the bottom color still has to be weighted by its own.
+        let color = if alpha > 0.0 {
+            (t + b * bottom_alpha * (1.0 - top_alpha)) / alpha
+        } else {
+            0.0
+        };
+        *bottom = denormalize(color);
+    }
+    channels[color_channels] = denormalize(alpha);
+}
+
+/// Returns the given subpixel scaled to the range 0.0 to 1.0.
+fn normalize<S: Primitive>(value: S) -> f32 {
+    value.to_f32().unwrap_or(0.0) / S::DEFAULT_MAX_VALUE.to_f32().unwrap_or(1.0)
+}
+
+/// Returns the subpixel for the given value in the range 0.0 to 1.0. Integer subpixels are rounded and clamped to their range,
+/// floating point subpixels have a maximum value of 1.0 and are kept as-is, even outside of it.
+fn denormalize<S: Primitive>(value: f32) -> S {
+    let max = S::DEFAULT_MAX_VALUE.to_f32().unwrap_or(1.0);
+    let value = value * max;
+    let value = if max > 1.0 {
+        value.round().clamp(0.0, max)
+    } else {
+        value
+    };
+    <S as NumCast>::from(value).unwrap_or(S::DEFAULT_MAX_VALUE)
+}
+
 /// The library's underlying resize method. This is only used internally and should not be used by the user, but is exposed
 /// through the raw module for documentation purposes.
 /// # Arguments
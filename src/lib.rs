@@ -2,11 +2,47 @@
 //! It is built on top of the image crate and works to boost performance by utilizing parallel processing and
 //! avoiding unnecessary costly operations.
 //!
-//! The main type of this crate is the [KnownSizeMerger](crate::KnownSizeMerger) struct, but, more will be added in the future.
+//! The main type of this crate is the [KnownSizeMerger](crate::KnownSizeMerger) struct. When the total number of images is not
+//! known ahead of time, the [GrowableMerger](crate::GrowableMerger) can be used instead, and canvases that are too large to fit
+//! in memory can be written straight to a PNG with the [StreamingPngMerger](crate::StreamingPngMerger). Images of different
+//! sizes can be packed into rows with the [PackingMerger](crate::PackingMerger).
+//!
+//! # Subpixel Types
+//! Mergers work with any [Pixel](image::Pixel), including 16 bit and floating point subpixels such as `Rgba<u16>` and
+//! `Rgba<f32>`. Pushing, removing, moving and cropping images copies their subpixels as-is, so these are lossless for every
+//! subpixel type, and floating point values outside of 0.0 to 1.0 are kept. Blending scales subpixels to floating point and
+//! back, which rounds integer subpixels and saturates them at their maximum value. Resizing with a filter is only as precise
+//! as the filter. Encoding is limited by the format, PNG only stores 8 and 16 bit subpixels while OpenEXR stores `f32`.
+//!
+//! # Features
+//! * `serde` - Implements `Serialize` and `Deserialize` for the [GrowableMerger](crate::GrowableMerger), so a merger can be
+//!   saved and restored later.
+//! * `async` - Adds [GrowableMerger::push_url](crate::GrowableMerger::push_url), which awaits the download of an image before
+//!   pushing it onto the canvas, and [GrowableMerger::push_url_with_spawner](crate::GrowableMerger::push_url_with_spawner),
+//!   which also decodes it on a blocking thread of your executor.
+//! * `log` - Emits [log](https://docs.rs/log) records when a [GrowableMerger](crate::GrowableMerger) resizes its canvas, at
+//!   the debug level, and when it pastes an image, at the trace level. Without the feature, no logging code is compiled in.
+//! * `testing` - Adds [Merger::assert_matches_golden](crate::Merger::assert_matches_golden), which compares a canvas
+//!   against a golden PNG on disk, for testing code that merges images.
+//! * `rayon` - Runs pasting, resizing and blending in parallel on the [rayon](https://docs.rs/rayon) thread pool. Bulk
+//!   pushes onto a [GrowableMerger](crate::GrowableMerger) split the canvas into bands of rows and paste into them at once.
+//!   This feature is enabled by default, as rayon used to be a required dependency. Turn it off with
+//!   `default-features = false` to drop the dependency, in which case everything runs on the calling thread.
+
+/// Emits a log record when the `log` feature is enabled, and expands to nothing otherwise, so the arguments are never
+/// evaluated.
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::$level!($($arg)+);
+    };
+}
+
 mod cell;
 mod core;
 mod functions;
 mod merger;
+mod parallel;
 
 pub use crate::core::*;
 pub use crate::merger::*;
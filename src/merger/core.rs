@@ -1,9 +1,33 @@
 use crate::core::Image;
-use image::Pixel;
-use std::{marker::Sync, ops::DerefMut};
+use crate::merger::MergeError;
+#[cfg(feature = "testing")]
+use crate::{BufferedImage, FromPath};
+use image::{
+    codecs::{
+        jpeg::JpegEncoder,
+        png::{self, CompressionType, PngEncoder},
+    },
+    error::{ParameterError, ParameterErrorKind},
+    imageops::{self, FilterType},
+    EncodableLayout, ImageBuffer, ImageError, ImageFormat, Pixel, PixelWithColorType,
+};
+#[cfg(feature = "testing")]
+use image::{
+    error::{ImageFormatHint, UnsupportedError, UnsupportedErrorKind},
+    io::Reader as ImageReader,
+    ImageDecoder,
+};
+use std::{
+    fs::File,
+    io::{BufWriter, Cursor},
+    marker::Sync,
+    ops::DerefMut,
+    path::Path,
+};
 
 /// Represents a point on any canvas.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: u32,
     pub y: u32,
@@ -15,6 +39,398 @@ pub struct Point {
 /// * `y` - The padding between images on the y axis.
 pub type Padding = Point;
 
+/// Represents the order that images are placed onto a canvas in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillOrder {
+    /// Images fill the canvas left to right, and then top to bottom once a row is full.
+    #[default]
+    RowMajor,
+    /// Images fill the canvas top to bottom, and then left to right once a column is full.
+    ColumnMajor,
+}
+
+/// Represents the direction that images advance in along the rows of a canvas, which decides whether the first image is
+/// placed at the left or the right edge of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HorizontalDirection {
+    /// The first image is placed in the leftmost column, and the images that follow move to the right.
+    #[default]
+    LeftToRight,
+    /// The first image is placed in the rightmost column, and the images that follow move to the left, such as for comics
+    /// that are read right to left.
+    RightToLeft,
+}
+
+/// Represents the direction that images advance in along the columns of a canvas, which decides whether the first image is
+/// placed at the top or the bottom edge of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerticalDirection {
+    /// The first image is placed in the top row, and the images that follow move down.
+    #[default]
+    TopToBottom,
+    /// The first image is placed in the bottom row, and the images that follow move up.
+    BottomToTop,
+}
+
+/// Represents where an image that is smaller than its place on a canvas is positioned within that place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellAlignment {
+    /// The image is placed in the top left corner of its place.
+    #[default]
+    TopLeft,
+    /// The image is placed at the top of its place, centered horizontally.
+    Top,
+    /// The image is placed in the top right corner of its place.
+    TopRight,
+    /// The image is placed at the left of its place, centered vertically.
+    Left,
+    /// The image is centered in its place.
+    Center,
+    /// The image is placed at the right of its place, centered vertically.
+    Right,
+    /// The image is placed in the bottom left corner of its place.
+    BottomLeft,
+    /// The image is placed at the bottom of its place, centered horizontally.
+    Bottom,
+    /// The image is placed in the bottom right corner of its place.
+    BottomRight,
+}
+
+impl CellAlignment {
+    /// Returns the offset, (x, y), from the top left corner of a place to the top left corner of an image aligned within it.
+    ///
+    /// # Arguments
+    /// * `outer` - The dimensions, (x, y), of the place.
+    /// * `inner` - The dimensions, (x, y), of the image. These must not be larger than the dimensions of the place.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::CellAlignment;
+    ///
+    /// assert_eq!(CellAlignment::Center.offset((100, 100), (50, 20)), (25, 40));
+    /// assert_eq!(CellAlignment::BottomLeft.offset((100, 100), (50, 20)), (0, 80));
+    /// ```
+    pub fn offset(&self, outer: (u32, u32), inner: (u32, u32)) -> (u32, u32) {
+        let (free_x, free_y) = (outer.0 - inner.0, outer.1 - inner.1);
+        let x = match self {
+            CellAlignment::TopLeft | CellAlignment::Left | CellAlignment::BottomLeft => 0,
+            CellAlignment::Top | CellAlignment::Center | CellAlignment::Bottom => free_x / 2,
+            CellAlignment::TopRight | CellAlignment::Right | CellAlignment::BottomRight => free_x,
+        };
+        let y = match self {
+            CellAlignment::TopLeft | CellAlignment::Top | CellAlignment::TopRight => 0,
+            CellAlignment::Left | CellAlignment::Center | CellAlignment::Right => free_y / 2,
+            CellAlignment::BottomLeft | CellAlignment::Bottom | CellAlignment::BottomRight => {
+                free_y
+            }
+        };
+        (x, y)
+    }
+}
+
+/// Represents where a watermark is drawn on a canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatermarkPosition {
+    /// The watermark is drawn once, in the top left corner of the canvas.
+    TopLeft,
+    /// The watermark is drawn once, in the top right corner of the canvas.
+    TopRight,
+    /// The watermark is drawn once, in the bottom left corner of the canvas.
+    BottomLeft,
+    /// The watermark is drawn once, in the bottom right corner of the canvas.
+    BottomRight,
+    /// The watermark is drawn once, in the center of the canvas.
+    Center,
+    /// The watermark is repeated across the whole canvas, starting in the top left corner.
+    Tiled,
+}
+
+/// Represents how much a growable canvas grows by when it is full and another image is pushed onto it. When filling in column
+/// major order, the canvas grows by columns instead of rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GrowthStrategy {
+    /// The canvas grows by a single row at a time. This wastes no space, but every row costs a reallocation.
+    #[default]
+    SingleRow,
+    /// The canvas grows by the given number of rows at a time. A value of zero is treated as a single row.
+    FixedRows(u32),
+    /// The canvas doubles the number of rows it holds every time it grows, so pushing `n` images one at a time only needs
+    /// `log2(n)` reallocations, at the cost of up to half of the canvas being empty.
+    Double,
+}
+
+impl GrowthStrategy {
+    /// Returns the number of rows to add to a canvas that currently holds the given number of rows. This is always at least one.
+    pub fn rows_to_add(&self, current_rows: u32) -> u32 {
+        match *self {
+            GrowthStrategy::SingleRow => 1,
+            GrowthStrategy::FixedRows(rows) => rows.max(1),
+            GrowthStrategy::Double => current_rows.max(1),
+        }
+    }
+}
+
+/// Represents what happens to the images after a removed one on a growable canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemovalMode {
+    /// Every image after the removed one is shifted one place towards the front of the canvas, so the images stay contiguous.
+    #[default]
+    Compact,
+    /// The place of the removed image is cleared to the background, and every other image stays where it is. This keeps the
+    /// position of each image meaningful, such as in a fixed gallery grid.
+    Leave,
+}
+
+/// Represents a rotation or flip that can be applied to an image before it is pasted onto a canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Rotates the image 90 degrees clockwise. This swaps the width and height of the image.
+    Rotate90,
+    /// Rotates the image 180 degrees.
+    Rotate180,
+    /// Rotates the image 270 degrees clockwise. This swaps the width and height of the image.
+    Rotate270,
+    /// Flips the image horizontally.
+    FlipHorizontal,
+    /// Flips the image vertically.
+    FlipVertical,
+}
+
+/// Represents how the pixels of an image are combined with the pixels already on a canvas when it is pasted with blending.
+///
+/// Every mode works with any pixel type. For pixel types with an alpha channel, `Add`, `Multiply` and `Screen` blend the color
+/// channels and combine the alpha channels like `Over` does, so they make the most sense for opaque layers. For pixel types
+/// without an alpha channel, `Over` simply replaces the pixels on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Standard source-over alpha compositing, the image is drawn on top of the canvas.
+    #[default]
+    Over,
+    /// Adds the channels of the image to the canvas, clamped to the maximum value of integer subpixels. Floating point
+    /// subpixels are not clamped, so they can go above 1.0. This brightens the canvas.
+    Add,
+    /// Multiplies the channels of the image with the canvas. This darkens the canvas.
+    Multiply,
+    /// Inverts both channels, multiplies them, and inverts the result. This brightens the canvas, but never past white.
+    Screen,
+    /// Source-over alpha compositing for an image whose color channels are already premultiplied by its alpha, such as the
+    /// output of many renderers. Compositing a premultiplied image with `Over` multiplies its colors by alpha a second time,
+    /// which leaves dark fringes around its transparent edges. The canvas keeps straight alpha. For pixel types without an
+    /// alpha channel, the image simply replaces the pixels on the canvas.
+    OverPremultiplied,
+}
+
+/// Represents the settings used to encode a canvas with [Merger::save_with_options](Merger::save_with_options). The default
+/// options match the defaults of the encoders in the image crate, so saving with them is the same as saving with `save`.
+/// # Fields
+/// * `jpeg_quality` - The quality of a JPEG, from 1 (worst) to 100 (best). The default is 75.
+/// * `png_compression` - How hard a PNG is compressed. Faster compression is useful for very large canvases, at the cost
+///   of a larger file.
+/// * `png_filter` - The filter applied to the rows of a PNG before they are compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub jpeg_quality: u8,
+    pub png_compression: CompressionType,
+    pub png_filter: png::FilterType,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            jpeg_quality: 75,
+            png_compression: CompressionType::default(),
+            png_filter: png::FilterType::default(),
+        }
+    }
+}
+
+/// Represents what the parts of a canvas that do not hold an image are filled with.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background<P> {
+    /// Fills the canvas with a single color.
+    Solid(P),
+    /// Fills the canvas with a checkerboard of two colors, the way image editors show transparency. The squares are aligned to
+    /// the top left corner of the canvas, starting with `a`.
+    Checker {
+        /// The color of the square in the top left corner.
+        a: P,
+        /// The color of the squares next to the ones colored `a`.
+        b: P,
+        /// The width and height of each square, in pixels.
+        size: u32,
+    },
+}
+
+impl<P: Pixel> Background<P> {
+    /// Returns the color of the background at the given position on the canvas.
+    ///
+    /// # Arguments
+    /// * `x` - The x coordinate on the canvas.
+    /// * `y` - The y coordinate on the canvas.
+    ///
+    /// # Panics
+    /// This function will panic if the background is a checkerboard with a square size of zero.
+    pub fn pixel_at(&self, x: u32, y: u32) -> P {
+        match *self {
+            Background::Solid(color) => color,
+            Background::Checker { a, b, size } => {
+                if (x / size) % 2 == (y / size) % 2 {
+                    a
+                } else {
+                    b
+                }
+            }
+        }
+    }
+}
+
+/// Decides where each image is placed on a canvas, and how large the canvas has to be to hold them. The
+/// [KnownSizeMerger](crate::KnownSizeMerger) places images with a layout, so implementing this trait allows for layouts such as
+/// brick walls with offset rows, spirals or diagonal mosaics. Every image on the canvas is the same size, and images must never
+/// overlap or reach past the edge of the canvas.
+///
+/// Layouts only apply to the [KnownSizeMerger](crate::KnownSizeMerger). The [GrowableMerger](crate::GrowableMerger) always
+/// places images on a grid, which it arranges with its own options such as `with_fill_order`, `with_fill_directions` and
+/// `with_padding`, because it moves and redraws images as the grid grows.
+pub trait Layout: Sync {
+    /// Returns the coordinates, (x, y), of the top left pixel of the image at the given index.
+    /// # Arguments
+    /// * `index` - The index of the image, starting at 0 and following the order images are pushed in.
+    /// * `image_dimensions` - The dimensions, (x, y), of the images on the canvas.
+    /// * `images_per_row` - The number of images per row the merger was constructed with.
+    fn coordinates(
+        &self,
+        index: u32,
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+    ) -> (u32, u32);
+
+    /// Returns the dimensions, (x, y), of a canvas that holds the given number of images.
+    /// # Arguments
+    /// * `num_images` - The number of images on the canvas.
+    /// * `image_dimensions` - The dimensions, (x, y), of the images on the canvas.
+    /// * `images_per_row` - The number of images per row the merger was constructed with.
+    fn canvas_size(
+        &self,
+        num_images: u32,
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+    ) -> (u32, u32);
+}
+
+/// Returns the indices of the pixels that differ between two buffers of subpixels of the pixel type `P`, in order.
+fn differing_pixels<'a, P: Pixel>(
+    subpixels: &'a [P::Subpixel],
+    other_subpixels: &'a [P::Subpixel],
+) -> impl Iterator<Item = usize> + 'a {
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    subpixels
+        .chunks_exact(channels)
+        .zip(other_subpixels.chunks_exact(channels))
+        .enumerate()
+        .filter_map(|(index, (pixel, other_pixel))| (pixel != other_pixel).then_some(index))
+}
+
+/// Checks that images of the given dimensions can be laid out in rows of the given number of images. Every grid merger checks
+/// this before it does any math with the number of images per row, which would divide by zero otherwise.
+pub(crate) fn check_layout(
+    image_dimensions: (u32, u32),
+    images_per_row: u32,
+) -> Result<(), MergeError> {
+    if images_per_row == 0 {
+        return Err(MergeError::InvalidConfiguration(
+            "there must be at least one image per row",
+        ));
+    }
+    if image_dimensions.0 == 0 || image_dimensions.1 == 0 {
+        return Err(MergeError::InvalidConfiguration(
+            "the images can not have a width or height of zero",
+        ));
+    }
+
+    Ok(())
+}
+
+/// The default [Layout](Layout), which places images left to right and then top to bottom once a row is full, with an optional
+/// padding between them. Zero images per row is treated as one, and coordinates and sizes that do not fit in a `u32` saturate,
+/// so the layout never panics when it is called directly.
+/// # Fields
+/// * `padding` - The padding between images, or `None` for no padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RowMajorLayout {
+    pub padding: Option<Padding>,
+}
+
+impl Layout for RowMajorLayout {
+    fn coordinates(
+        &self,
+        index: u32,
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+    ) -> (u32, u32) {
+        let images_per_row = images_per_row.max(1);
+        let offset_x = index % images_per_row;
+        let offset_y = index / images_per_row;
+
+        let padding_x = self
+            .padding
+            .as_ref()
+            .map(|p| p.x)
+            .unwrap_or(0)
+            .saturating_mul(offset_x);
+        let padding_y = self
+            .padding
+            .as_ref()
+            .map(|p| p.y)
+            .unwrap_or(0)
+            .saturating_mul(offset_y);
+
+        let x = offset_x
+            .saturating_mul(image_dimensions.0)
+            .saturating_add(padding_x);
+        let y = offset_y
+            .saturating_mul(image_dimensions.1)
+            .saturating_add(padding_y);
+
+        (x, y)
+    }
+
+    fn canvas_size(
+        &self,
+        num_images: u32,
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+    ) -> (u32, u32) {
+        let images_per_row = images_per_row.max(1);
+        let total_rows = num_images.div_ceil(images_per_row);
+
+        let image_gaps_x =
+            (images_per_row - 1).saturating_mul(self.padding.as_ref().map(|p| p.x).unwrap_or(0));
+        let image_gaps_y = total_rows
+            .saturating_sub(1)
+            .saturating_mul(self.padding.as_ref().map(|p| p.y).unwrap_or(0));
+
+        (
+            image_dimensions
+                .0
+                .saturating_mul(images_per_row)
+                .saturating_add(image_gaps_x),
+            image_dimensions
+                .1
+                .saturating_mul(total_rows)
+                .saturating_add(image_gaps_y),
+        )
+    }
+}
+
 /// The Merger trait that all mergers must implement. This trait allows the merger to paste images to a canvas.
 /// # Type Parameters
 /// * `P` - The pixel type of the underlying image.
@@ -24,24 +440,348 @@ where
     <P as Pixel>::Subpixel: Sync,
     Container: DerefMut<Target = [P::Subpixel]> + Sync,
 {
-    /// Returns a reference to the underlying canvas.
+    /// Returns a reference to the underlying canvas. The canvas dereferences to an [image::ImageBuffer](image::ImageBuffer),
+    /// so it can be inspected or processed further with the image crate.
     fn get_canvas(&self) -> &Image<P, image::ImageBuffer<P, Container>>;
 
-    /// Consumes the underlying merger and returns the canvas.
+    /// Consumes the underlying merger and returns the canvas. Use [Image::into_buffer](Image::into_buffer) to get the underlying
+    /// [image::ImageBuffer](image::ImageBuffer) without a clone.
     fn into_canvas(self) -> Image<P, image::ImageBuffer<P, Container>>;
 
     /// Allows the merger to push an image to the canvas. This can be used in a loop to paste a large number of images without
-    /// having to hold all them in memory.
+    /// having to hold all them in memory. The image's pixels are copied onto the canvas without any alpha blending, so this is
+    /// already the fast path for opaque images. To alpha blend an image over the canvas instead, use
+    /// [GrowableMerger::push_blended](crate::GrowableMerger::push_blended) with [BlendMode::Over](BlendMode::Over).
     /// # Arguments
     /// * `image` - The image to push onto the canvas. Its pixel type, `P`, must match the canvas, and its `Container` must be dereferenceable to
-    /// a slice of `P::Subpixel`s.
+    ///   a slice of `P::Subpixel`s.
     fn push(&mut self, image: &Image<P, image::ImageBuffer<P, Container>>);
 
     /// Allows the merger to bulk push N images to the canvas. This is useful for when you have a large number of images to paste.
     /// The downside is that you have to hold all of the images in memory at once, which can be a problem if you have a large number of images.
     /// # Arguments
     /// * `images` - The images to push onto the canvas. Note that the argument type is `&[&Image<...>]`, the func
-    /// does not need to take ownership of the images, it only needs to read them. The pixel type, `P`, of the images must match the canvas, and
-    /// their `Container` must be dereferenceable to a slice of `P::Subpixel`s.
+    ///   does not need to take ownership of the images, it only needs to read them. The pixel type, `P`, of the images must match the canvas, and
+    ///   their `Container` must be dereferenceable to a slice of `P::Subpixel`s.
     fn bulk_push(&mut self, images: &[&Image<P, image::ImageBuffer<P, Container>>]);
+
+    /// Saves the canvas to a file at the given path. The image format is derived from the file extension.
+    /// # Arguments
+    /// * `path` - The path to save the canvas to.
+    /// # Returns
+    /// An error if the canvas could not be encoded in the requested format or could not be written to the given path.
+    fn save<Q>(&self, path: Q) -> image::ImageResult<()>
+    where
+        Q: AsRef<Path>,
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        self.get_canvas().save(path)
+    }
+
+    /// Saves the canvas to a file at the given path like `save`, with control over how it is encoded. The image format is
+    /// derived from the file extension. The options only apply to JPEGs and PNGs, any other format is saved as-is.
+    /// # Arguments
+    /// * `path` - The path to save the canvas to.
+    /// * `options` - The settings to encode the canvas with.
+    /// # Returns
+    /// An error if the JPEG quality is not between 1 and 100, or if the canvas could not be encoded in the requested format or
+    /// could not be written to the given path.
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, EncodeOptions, Rgb};
+    /// use image::codecs::png::CompressionType;
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// let path = std::env::temp_dir().join("image_merger_save_with_options_example.png");
+    /// let options = EncodeOptions {
+    ///     png_compression: CompressionType::Fast,
+    ///     ..EncodeOptions::default()
+    /// };
+    /// merger.save_with_options(&path, options).unwrap();
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    fn save_with_options<Q>(&self, path: Q, options: EncodeOptions) -> image::ImageResult<()>
+    where
+        Q: AsRef<Path>,
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        let path = path.as_ref();
+        let canvas = self.get_canvas();
+        match ImageFormat::from_path(path)? {
+            ImageFormat::Jpeg => {
+                if !(1..=100).contains(&options.jpeg_quality) {
+                    return Err(ImageError::Parameter(ParameterError::from_kind(
+                        ParameterErrorKind::Generic(format!(
+                            "the JPEG quality must be between 1 and 100, got {}",
+                            options.jpeg_quality
+                        )),
+                    )));
+                }
+
+                let writer = BufWriter::new(File::create(path)?);
+                canvas
+                    .write_with_encoder(JpegEncoder::new_with_quality(writer, options.jpeg_quality))
+            }
+            ImageFormat::Png => {
+                let writer = BufWriter::new(File::create(path)?);
+                canvas.write_with_encoder(PngEncoder::new_with_quality(
+                    writer,
+                    options.png_compression,
+                    options.png_filter,
+                ))
+            }
+            format => canvas.save_with_format(path, format),
+        }
+    }
+
+    /// Encodes the canvas in the given image format and returns the encoded bytes, without touching the filesystem.
+    /// # Arguments
+    /// * `format` - The image format to encode the canvas in.
+    /// # Returns
+    /// The encoded bytes, or an error if the canvas could not be encoded in the requested format, for example because the
+    /// format does not support the pixel type of the canvas.
+    fn encode(&self, format: ImageFormat) -> image::ImageResult<Vec<u8>>
+    where
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        let mut bytes = Cursor::new(Vec::new());
+        self.get_canvas().write_to(&mut bytes, format)?;
+        Ok(bytes.into_inner())
+    }
+
+    /// Returns a copy of a rectangular region of the canvas. The region is clamped to the bounds of the canvas, so a region
+    /// that reaches past the right or bottom edge is cut off there.
+    /// # Arguments
+    /// * `x` - The x coordinate of the top left corner of the region.
+    /// * `y` - The y coordinate of the top left corner of the region.
+    /// * `width` - The width of the region.
+    /// * `height` - The height of the region.
+    /// # Returns
+    /// The pixels of the region, or an error if the region is empty or starts outside of the canvas.
+    fn crop_region(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> image::ImageResult<ImageBuffer<P, Vec<P::Subpixel>>> {
+        let canvas = self.get_canvas();
+        let (canvas_width, canvas_height) = canvas.dimensions();
+        if width == 0 || height == 0 || x >= canvas_width || y >= canvas_height {
+            return Err(ImageError::Parameter(ParameterError::from_kind(
+                ParameterErrorKind::DimensionMismatch,
+            )));
+        }
+
+        let width = width.min(canvas_width - x);
+        let height = height.min(canvas_height - y);
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let row_len = width as usize * channels;
+
+        let buffer: &[P::Subpixel] = canvas.as_raw();
+        let mut container = Vec::with_capacity(row_len * height as usize);
+        for row in y as usize..(y + height) as usize {
+            let start = (row * canvas_width as usize + x as usize) * channels;
+            container.extend_from_slice(&buffer[start..start + row_len]);
+        }
+
+        // Can always unwrap here because the buffer is always the right size.
+        Ok(ImageBuffer::from_raw(width, height, container).unwrap())
+    }
+
+    /// Returns a copy of the canvas scaled down so its largest side is `max_dimension` pixels, keeping its aspect ratio. This
+    /// is useful for making a preview of a very large canvas. The canvas itself is left untouched, and a canvas that already
+    /// fits within `max_dimension` is copied as-is rather than scaled up.
+    /// # Arguments
+    /// * `max_dimension` - The largest the width or height of the copy is allowed to be.
+    /// * `filter` - The filter used to scale the canvas down.
+    /// # Panics
+    /// This function will panic if `max_dimension` is zero.
+    fn downscaled(&self, max_dimension: u32, filter: FilterType) -> ImageBuffer<P, Vec<P::Subpixel>>
+    where
+        P: 'static,
+    {
+        if max_dimension == 0 {
+            panic!("A canvas can not be scaled down to a size of zero.");
+        }
+
+        let canvas = self.get_canvas();
+        let (width, height) = canvas.dimensions();
+        let largest = width.max(height);
+        if largest <= max_dimension {
+            return ImageBuffer::from_raw(width, height, canvas.as_raw().to_vec()).unwrap();
+        }
+
+        // The smaller side is rounded to the nearest pixel, but never scaled down to nothing.
+        let scale = |side: u32| -> u32 {
+            ((side as u64 * max_dimension as u64 + largest as u64 / 2) / largest as u64).max(1)
+                as u32
+        };
+        imageops::resize(&**canvas, scale(width), scale(height), filter)
+    }
+
+    /// Returns a 64 bit FNV-1a hash of the dimensions and subpixels of the canvas. Two canvases with the same dimensions and
+    /// pixels always hash the same, no matter which merger made them or in which order the images were pushed, so this can be
+    /// used to skip encoding a canvas that has not changed. The subpixels are hashed as little endian bytes, which makes the
+    /// hash stable across runs and platforms. This is not a cryptographic hash.
+    fn content_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+        let hash_byte = |hash: u64, byte: u8| (hash ^ byte as u64).wrapping_mul(PRIME);
+
+        let canvas = self.get_canvas();
+        let mut hash = canvas
+            .width()
+            .to_le_bytes()
+            .into_iter()
+            .chain(canvas.height().to_le_bytes())
+            .fold(OFFSET_BASIS, hash_byte);
+
+        let subpixels: &[P::Subpixel] = canvas;
+        let size = std::mem::size_of::<P::Subpixel>();
+        // Safety: subpixels are plain numbers without any padding, so their memory can always be read as bytes.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                subpixels.as_ptr() as *const u8,
+                std::mem::size_of_val(subpixels),
+            )
+        };
+        for subpixel in bytes.chunks_exact(size) {
+            hash = if cfg!(target_endian = "big") {
+                subpixel.iter().rev().copied().fold(hash, hash_byte)
+            } else {
+                subpixel.iter().copied().fold(hash, hash_byte)
+            };
+        }
+        hash
+    }
+
+    /// Returns true if the canvas of this merger holds exactly the same pixels as the canvas of another merger. The
+    /// dimensions are compared first, so canvases of different sizes are never scanned.
+    /// # Arguments
+    /// * `other` - The merger to compare against. It can be a different kind of merger, as long as its pixel type matches.
+    fn pixels_equal<M, OtherContainer>(&self, other: &M) -> bool
+    where
+        M: Merger<P, OtherContainer>,
+        OtherContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let (canvas, other_canvas) = (self.get_canvas(), other.get_canvas());
+        if canvas.dimensions() != other_canvas.dimensions() {
+            return false;
+        }
+
+        let subpixels: &[P::Subpixel] = canvas;
+        let other_subpixels: &[P::Subpixel] = other_canvas;
+        subpixels == other_subpixels
+    }
+
+    /// Compares the canvas against a golden image on disk, for testing code that merges images. The golden image is decoded
+    /// with the image crate, and must have the same color type as the canvas. On a mismatch, the canvas is saved next to the
+    /// golden image with `.actual` added before its extension, so the two can be compared side by side, and a new golden
+    /// image can be made by renaming it.
+    /// # Arguments
+    /// * `path` - The path of the golden image, usually a PNG.
+    /// # Returns
+    /// * `Ok` - If the canvas holds exactly the same pixels as the golden image.
+    /// * `Err` - A [MergeError::GoldenMismatch](MergeError::GoldenMismatch) with the first pixel that differs and the number
+    ///   of pixels that differ, a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) where `expected` is the size
+    ///   of the golden image if the sizes do not match, or a [MergeError::Image](MergeError::Image) if the golden image
+    ///   could not be read or has a different color type than the canvas.
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, MergeError, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// let path = std::env::temp_dir().join("image_merger_golden_example.png");
+    /// merger.save(&path).unwrap();
+    /// assert!(merger.assert_matches_golden(&path).is_ok());
+    ///
+    /// merger.push_at(1, &BufferedImage::new_from_pixel(100, 100, Rgb([255, 0, 0]))).unwrap();
+    /// let err = merger.assert_matches_golden(&path).unwrap_err();
+    /// assert!(matches!(err, MergeError::GoldenMismatch { first_diff: (100, 0), differing_pixels: 10000, .. }));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// # std::fs::remove_file(std::env::temp_dir().join("image_merger_golden_example.actual.png")).unwrap();
+    /// ```
+    #[cfg(feature = "testing")]
+    fn assert_matches_golden<Q>(&self, path: Q) -> Result<(), MergeError>
+    where
+        Q: AsRef<Path>,
+        P: PixelWithColorType + 'static,
+        [P::Subpixel]: EncodableLayout,
+        BufferedImage<P>: FromPath,
+    {
+        let path = path.as_ref();
+        // Only the header is read here, loading the golden image would silently convert it to the color type of the canvas.
+        let color = ImageReader::open(path)
+            .map_err(ImageError::IoError)?
+            .with_guessed_format()
+            .map_err(ImageError::IoError)?
+            .into_decoder()?
+            .color_type();
+        if image::ExtendedColorType::from(color) != P::COLOR_TYPE {
+            return Err(MergeError::Image(ImageError::Unsupported(
+                UnsupportedError::from_format_and_kind(
+                    ImageFormatHint::Unknown,
+                    UnsupportedErrorKind::Color(color.into()),
+                ),
+            )));
+        }
+
+        let golden = BufferedImage::<P>::from_path(path)?;
+        let canvas = self.get_canvas();
+        if golden.dimensions() != canvas.dimensions() {
+            return Err(MergeError::DimensionMismatch {
+                expected: golden.dimensions(),
+                got: canvas.dimensions(),
+            });
+        }
+
+        let mut differences = differing_pixels::<P>(canvas, &golden);
+        let Some(index) = differences.next() else {
+            return Ok(());
+        };
+        let width = canvas.width() as usize;
+        let first_diff = ((index % width) as u32, (index / width) as u32);
+        let differing_pixels = 1 + differences.count() as u64;
+
+        let mut name = path.file_stem().unwrap_or_default().to_os_string();
+        name.push(".actual.");
+        name.push(path.extension().unwrap_or_default());
+        let actual = path.with_file_name(name);
+        Err(MergeError::GoldenMismatch {
+            first_diff,
+            differing_pixels,
+            actual: self.save(&actual).is_ok().then_some(actual),
+        })
+    }
+
+    /// Finds the first pixel, going left to right and top to bottom, that differs between the canvas of this merger and the
+    /// canvas of another merger. This is useful for finding out where two canvases that should match went wrong.
+    /// # Arguments
+    /// * `other` - The merger to compare against. It can be a different kind of merger, as long as its pixel type matches.
+    /// # Returns
+    /// * `Ok` - The coordinates, (x, y), of the first pixel that differs, or `None` if the canvases are identical.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) where `expected` is the size of the other
+    ///   canvas if the canvases are different sizes. They are not scanned in this case.
+    fn first_diff<M, OtherContainer>(&self, other: &M) -> Result<Option<(u32, u32)>, MergeError>
+    where
+        M: Merger<P, OtherContainer>,
+        OtherContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let (canvas, other_canvas) = (self.get_canvas(), other.get_canvas());
+        if canvas.dimensions() != other_canvas.dimensions() {
+            return Err(MergeError::DimensionMismatch {
+                expected: other_canvas.dimensions(),
+                got: canvas.dimensions(),
+            });
+        }
+
+        let width = canvas.width() as usize;
+        Ok(differing_pixels::<P>(canvas, other_canvas)
+            .next()
+            .map(|index| ((index % width) as u32, (index / width) as u32)))
+    }
 }
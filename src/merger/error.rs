@@ -0,0 +1,167 @@
+use std::{error::Error, fmt, path::PathBuf};
+
+/// Represents an error that can occur while merging images.
+#[derive(Debug)]
+pub enum MergeError {
+    /// The dimensions of an image did not match the dimensions of the images on the canvas.
+    /// # Fields
+    /// * `expected` - The dimensions, (x, y), of the images on the canvas.
+    /// * `got` - The dimensions, (x, y), of the image that was given.
+    DimensionMismatch {
+        expected: (u32, u32),
+        got: (u32, u32),
+    },
+    /// There is no image on the canvas at the given index.
+    /// # Fields
+    /// * `index` - The index that was given.
+    /// * `num_images` - The number of images on the canvas.
+    IndexOutOfBounds { index: u32, num_images: u32 },
+    /// Two mergers could not be combined because they have a different number of images per row.
+    /// # Fields
+    /// * `expected` - The number of images per row of the merger being added to.
+    /// * `got` - The number of images per row of the merger that was given.
+    ImagesPerRowMismatch { expected: u32, got: u32 },
+    /// The canvas is full and is not allowed to grow.
+    /// # Fields
+    /// * `capacity` - The number of images the canvas can hold.
+    OutOfCapacity { capacity: u32 },
+    /// Growing the canvas would make it larger than it is allowed to be.
+    /// # Fields
+    /// * `max_dimensions` - The maximum dimensions, (x, y), of the canvas, or `None` if the canvas would be too large to
+    ///   fit in memory at all.
+    CanvasTooLarge { max_dimensions: Option<(u32, u32)> },
+    /// There was not enough memory to allocate the canvas.
+    /// # Fields
+    /// * `bytes` - The number of bytes that could not be allocated.
+    AllocationFailed { bytes: usize },
+    /// An image is wider than the rows of the canvas it is being packed onto.
+    /// # Fields
+    /// * `max_width` - The width of the rows of the canvas.
+    /// * `got` - The width of the image that was given.
+    ImageTooWide { max_width: u32, got: u32 },
+    /// A raw buffer does not hold exactly as many subpixels as an image of its dimensions needs.
+    /// # Fields
+    /// * `expected` - The number of subpixels an image of the given dimensions holds, or `usize::MAX` if it is too large to
+    ///   count.
+    /// * `got` - The number of subpixels in the buffer that was given.
+    BufferSizeMismatch { expected: usize, got: usize },
+    /// A region does not lie completely within the image it was taken from.
+    /// # Fields
+    /// * `region` - The x and y coordinates of the top left corner of the region, and its width and height.
+    /// * `dimensions` - The dimensions, (x, y), of the image.
+    RegionOutOfBounds {
+        region: (u32, u32, u32, u32),
+        dimensions: (u32, u32),
+    },
+    /// A merger was configured with options that can not be used, or together.
+    /// # Fields
+    /// * `0` - What is wrong with the configuration.
+    InvalidConfiguration(&'static str),
+    /// No images were given to an operation that needs at least one.
+    NoImages,
+    /// A canvas does not hold the same pixels as the golden image it was compared against.
+    /// # Fields
+    /// * `first_diff` - The coordinates, (x, y), of the first pixel that differs.
+    /// * `differing_pixels` - The number of pixels that differ.
+    /// * `actual` - Where the canvas was written to so it can be inspected, or `None` if it could not be written.
+    ///
+    /// This is only returned by `Merger::assert_matches_golden`, which needs the `testing` feature, but the variant exists in
+    /// every build so enabling the feature never changes this enum.
+    GoldenMismatch {
+        first_diff: (u32, u32),
+        differing_pixels: u64,
+        actual: Option<PathBuf>,
+    },
+    /// An image could not be read or decoded.
+    Image(image::ImageError),
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::DimensionMismatch { expected, got } => write!(
+                f,
+                "Expected an image of size {}x{}, but got an image of size {}x{}.",
+                expected.0, expected.1, got.0, got.1
+            ),
+            MergeError::IndexOutOfBounds { index, num_images } => write!(
+                f,
+                "There is no image at index {}, the canvas only holds {} images.",
+                index, num_images
+            ),
+            MergeError::ImagesPerRowMismatch { expected, got } => write!(
+                f,
+                "Expected a merger with {} images per row, but got a merger with {} images per row.",
+                expected, got
+            ),
+            MergeError::OutOfCapacity { capacity } => write!(
+                f,
+                "The canvas is full, it can only hold {} images.",
+                capacity
+            ),
+            MergeError::CanvasTooLarge {
+                max_dimensions: Some(max),
+            } => write!(
+                f,
+                "The canvas can not grow larger than {}x{}.",
+                max.0, max.1
+            ),
+            MergeError::CanvasTooLarge {
+                max_dimensions: None,
+            } => write!(f, "The canvas would grow too large to fit in memory."),
+            MergeError::AllocationFailed { bytes } => {
+                write!(f, "Could not allocate {} bytes for the canvas.", bytes)
+            }
+            MergeError::ImageTooWide { max_width, got } => write!(
+                f,
+                "The image is {} pixels wide, but the rows of the canvas are only {} pixels wide.",
+                got, max_width
+            ),
+            MergeError::BufferSizeMismatch { expected, got } => write!(
+                f,
+                "Expected a buffer of {} subpixels, but got a buffer of {} subpixels.",
+                expected, got
+            ),
+            MergeError::RegionOutOfBounds { region, dimensions } => write!(
+                f,
+                "The {}x{} region at ({}, {}) does not fit within the {}x{} image.",
+                region.2, region.3, region.0, region.1, dimensions.0, dimensions.1
+            ),
+            MergeError::InvalidConfiguration(reason) => {
+                write!(f, "The merger is configured incorrectly: {}.", reason)
+            }
+            MergeError::NoImages => write!(f, "At least one image must be given."),
+            MergeError::GoldenMismatch {
+                first_diff,
+                differing_pixels,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "The canvas does not match the golden image, {} pixels differ, starting at ({}, {}).",
+                    differing_pixels, first_diff.0, first_diff.1
+                )?;
+                match actual {
+                    Some(path) => write!(f, " The canvas was written to {}.", path.display()),
+                    None => write!(f, " The canvas could not be written out."),
+                }
+            }
+            MergeError::Image(err) => write!(f, "Could not load the image: {}", err),
+        }
+    }
+}
+
+impl Error for MergeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MergeError::Image(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<image::ImageError> for MergeError {
+    fn from(err: image::ImageError) -> Self {
+        MergeError::Image(err)
+    }
+}
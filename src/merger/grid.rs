@@ -0,0 +1,109 @@
+use super::{core::Merger, error::MergeError, growable::GrowableMerger};
+use crate::{BufferedImage, Image};
+
+use image::{GenericImage, ImageBuffer, ImageFormat, Pixel, Rgba};
+use std::io::Cursor;
+
+/// Merges the given images into a grid in one call, without having to manage a merger. The image dimensions are taken from
+/// the first image, and the canvas is sized to fit the images exactly. When there are fewer images than `images_per_row`, the
+/// canvas is only as wide as the images it holds.
+///
+/// # Arguments
+/// * `images` - The images to merge, in the order they are placed onto the canvas. Every image must be the same size.
+/// * `images_per_row` - The number of images per row.
+///
+/// # Returns
+/// * `Ok` - The finished canvas.
+/// * `Err` - A [MergeError::NoImages](MergeError::NoImages) if no images were given, a
+///   [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the images are not all the same size, a
+///   [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` is zero or the images have a
+///   width or height of zero, or a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would be too large
+///   to fit in memory.
+///
+/// # Example
+/// ```
+/// use image_merger::{merge_grid, BufferedImage, Rgb};
+///
+/// let images: Vec<BufferedImage<Rgb<u8>>> = (0..12).map(|_| BufferedImage::new(100, 100)).collect();
+/// let canvas = merge_grid(&images, 5).unwrap();
+/// assert_eq!(canvas.dimensions(), (500, 300));
+/// ```
+pub fn merge_grid<P, U>(
+    images: &[Image<P, U>],
+    images_per_row: u32,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, MergeError>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+    U: GenericImage<Pixel = P>,
+{
+    let first = images.first().ok_or(MergeError::NoImages)?;
+    let image_dimensions = first.dimensions();
+    if let Some(image) = images
+        .iter()
+        .find(|image| image.dimensions() != image_dimensions)
+    {
+        return Err(MergeError::DimensionMismatch {
+            expected: image_dimensions,
+            got: image.dimensions(),
+        });
+    }
+
+    // A single row is trimmed to the number of images in it, so the canvas never ends with empty places.
+    let num_images = u32::try_from(images.len()).unwrap_or(u32::MAX);
+    let mut merger: GrowableMerger<P> = GrowableMerger::builder()
+        .image_dimensions(image_dimensions)
+        .images_per_row(images_per_row.min(num_images))
+        .capacity(num_images)
+        .build()?;
+
+    for image in images {
+        let buffered: BufferedImage<P> =
+            ImageBuffer::from_fn(image.width(), image.height(), |x, y| image.get_pixel(x, y))
+                .into();
+        merger.try_push(&buffered)?;
+    }
+    Ok(merger.into_canvas().into_buffer())
+}
+
+/// Merges PNG images into a grid and returns the canvas encoded as a PNG, without touching the filesystem. This works anywhere
+/// the image crate does, including WebAssembly, so it can be exported as a single function. The images are decoded to 8 bit
+/// RGBA, and merged like [merge_grid](merge_grid) does.
+///
+/// # Arguments
+/// * `images` - The encoded PNG images to merge, in the order they are placed onto the canvas. Every image must be the same
+///   size.
+/// * `images_per_row` - The number of images per row.
+///
+/// # Returns
+/// * `Ok` - The encoded PNG of the canvas.
+/// * `Err` - A [MergeError::Image](MergeError::Image) if an image is not a valid PNG or the canvas could not be encoded, or
+///   any of the errors of [merge_grid](merge_grid).
+///
+/// # Example
+/// ```
+/// use image_merger::{merge_png_bytes, BufferedImage, Merger, GrowableMerger, Rgba};
+/// use image::ImageFormat;
+///
+/// let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 1);
+/// merger.push(&BufferedImage::new(100, 100));
+/// let png = merger.encode(ImageFormat::Png).unwrap();
+///
+/// let merged = merge_png_bytes(&[png.clone(), png.clone(), png], 2).unwrap();
+/// let canvas = image::load_from_memory(&merged).unwrap();
+/// assert_eq!((canvas.width(), canvas.height()), (200, 200));
+/// ```
+pub fn merge_png_bytes(images: &[Vec<u8>], images_per_row: u32) -> Result<Vec<u8>, MergeError> {
+    let decoded = images
+        .iter()
+        .map(|bytes| {
+            image::load_from_memory_with_format(bytes, ImageFormat::Png)
+                .map(|image| Image::from(image.to_rgba8()))
+        })
+        .collect::<Result<Vec<BufferedImage<Rgba<u8>>>, _>>()?;
+    let canvas = merge_grid(&decoded, images_per_row)?;
+
+    let mut bytes = Vec::new();
+    canvas.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?;
+    Ok(bytes)
+}
@@ -0,0 +1,4203 @@
+use super::{
+    core::{
+        check_layout, Background, BlendMode, CellAlignment, FillOrder, GrowthStrategy,
+        HorizontalDirection, Merger, Padding, Point, RemovalMode, Transform, VerticalDirection,
+        WatermarkPosition,
+    },
+    error::MergeError,
+};
+use crate::{
+    cell::ImageCell,
+    functions::{blend_pixel, blend_pixel_with_opacity, paste, resize_nearest_neighbor},
+    parallel::{self, prelude::*},
+    BufferedImage, FromBytes, FromPath, Image, ResizableMerger,
+};
+
+use image::{
+    buffer::ConvertBuffer,
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::{self, FilterType},
+    Delay, EncodableLayout, Frame, ImageBuffer, Pixel, PixelWithColorType, RgbaImage, SubImage,
+};
+use num_traits::Zero;
+use std::{
+    collections::BTreeSet,
+    io::Write,
+    ops::{DerefMut, Range},
+    path::Path,
+    time::Duration,
+};
+
+mod builder;
+
+pub use builder::*;
+
+/// A view into the canvas of a [GrowableMerger](GrowableMerger) covering a single image, as returned by
+/// [GrowableMerger::tiles](GrowableMerger::tiles).
+pub type Tile<'a, P> = SubImage<&'a ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>>;
+
+/// A growable merger that allows you to paste images onto a canvas without knowing how many images will be pasted ahead of time.
+/// Like the [KnownSizeMerger](crate::KnownSizeMerger), all images being pushed onto the canvas must be of a uniform size, but
+/// the canvas starts with a single row and grows row by row as images are pushed onto it. If the merger is set to fill the canvas
+/// in [FillOrder::ColumnMajor](FillOrder::ColumnMajor) order instead, the canvas starts with a single column and grows column by
+/// column. Because the canvas must be able to grow, this merger is always backed by a `Vec`.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+///
+/// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+/// let image = BufferedImage::new(100, 100);
+/// merger.bulk_push(&[&image; 12]);
+/// assert_eq!(merger.get_canvas().height(), 300);
+/// ```
+pub struct GrowableMerger<P>
+where
+    P: Pixel,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    canvas: ImageCell<P, image::ImageBuffer<P, Vec<P::Subpixel>>>,
+    image_dimensions: (u32, u32), // The dimensions of the images being pasted (images must be a uniform size)
+    num_images: u32,              // The number of images that have been pasted to the canvas
+    images_per_line: u32, // The number of images per row, or per column when filling in column major order.
+    last_pasted_index: Option<u32>, // The index of the last pasted image, `None` if no images have been pasted.
+    holes: BTreeSet<u32>, // The places before the last image that do not hold one, left by `push_at` or removals.
+    // The total number of rows (or columns in column major order) currently allocated on the canvas. This is never zero, an
+    // empty merger always has a canvas with a single row, so the first row of pushes never has to grow the canvas.
+    total_lines: u32,
+    fill_order: FillOrder,
+    // The directions images advance in along the rows and the columns, which decide the corner the first image is placed in.
+    fill_directions: (HorizontalDirection, VerticalDirection),
+    fixed_size: bool, // Whether the canvas is allowed to grow once it is full.
+    growth_strategy: GrowthStrategy, // How many lines are added when the canvas grows because it is full.
+    max_canvas_dimensions: Option<(u32, u32)>, // The largest the canvas is allowed to grow to, if limited.
+    padding: Option<Padding>,
+    background: Option<Background<P>>, // What any space on the canvas without an image is filled with, zero if not set.
+    border: Option<(u32, P)>, // The thickness and color of the border around each image, if any.
+    caption_height: u32, // The height of the strip reserved below each place for a caption, zero if there is none.
+    tight_last_row: bool, // Whether trimmed copies of the canvas crop or clear the empty places at the end of the last line.
+    header: Option<BufferedImage<P>>, // The band drawn above the grid, if any.
+    footer: Option<BufferedImage<P>>, // The band drawn below the grid, if any.
+    resize_filter: Option<FilterType>, // The filter used to resize pushed images that are the wrong size, if any.
+    preserve_aspect_ratio: bool,
+    cell_alignment: Option<CellAlignment>, // Where images smaller than their place are positioned, if they are accepted.
+    progress_callback: Option<ProgressCallback>, // Called with (done, total) while bulk operations make progress, if set.
+    memory_budget: Option<usize>, // The most bytes the canvas may take up before completed rows are flushed, if limited.
+    flush_callback: Option<FlushCallback<P>>, // Called with the completed rows that are flushed off of the canvas.
+    flushed_rows: u32, // The number of rows that have been flushed off of the top of the canvas.
+}
+
+type ProgressCallback = Box<dyn FnMut(u32, u32) + Send + Sync>;
+type FlushCallback<P> = Box<dyn FnMut(ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>) + Send + Sync>;
+
+impl<P> GrowableMerger<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    /// Constructs a new GrowableMerger with a canvas that can hold a single row of images. The canvas will grow as more
+    /// images are pushed onto it.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row. When filling in column major order, this is the number of images
+    ///   per column instead.
+    ///
+    /// # Panics
+    /// This function will panic if `images_per_row` or either of the image dimensions is zero.
+    pub fn new(image_dimensions: (u32, u32), images_per_row: u32) -> Self {
+        Self::with_capacity(image_dimensions, images_per_row, images_per_row)
+    }
+
+    /// Constructs a new GrowableMerger like `new`, but returns an error instead of panicking if it can not be laid out.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row. When filling in column major order, this is the number of images
+    ///   per column instead.
+    ///
+    /// # Returns
+    /// * `Ok` - The merger.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` or either of the
+    ///   image dimensions is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{GrowableMerger, MergeError, Rgb};
+    ///
+    /// let merger = GrowableMerger::<Rgb<u8>>::try_new((100, 100), 0);
+    /// assert!(matches!(merger, Err(MergeError::InvalidConfiguration(_))));
+    /// ```
+    pub fn try_new(image_dimensions: (u32, u32), images_per_row: u32) -> Result<Self, MergeError> {
+        check_layout(image_dimensions, images_per_row)?;
+        Ok(Self::new(image_dimensions, images_per_row))
+    }
+
+    /// Returns a [GrowableMergerBuilder](GrowableMergerBuilder) to configure a new GrowableMerger with, which checks the
+    /// options before the canvas is allocated instead of panicking.
+    pub fn builder() -> GrowableMergerBuilder<P> {
+        GrowableMergerBuilder::new()
+    }
+
+    /// Constructs a new GrowableMerger with a canvas that is already large enough to hold the expected number of images, so
+    /// the canvas does not have to grow while they are pushed. Pushing more images than expected will still grow the canvas.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row. When filling in column major order, this is the number of images
+    ///   per column instead.
+    /// * `expected_images` - The number of images the canvas should be able to hold before it needs to grow.
+    ///
+    /// # Panics
+    /// This function will panic if `images_per_row` or either of the image dimensions is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 10, 95);
+    /// assert_eq!(merger.get_canvas().height(), 1000);
+    /// ```
+    pub fn with_capacity(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        expected_images: u32,
+    ) -> Self {
+        Self::check_layout(image_dimensions, images_per_row);
+
+        // The canvas always holds at least one row, even if no images are expected.
+        let total_lines = expected_images.div_ceil(images_per_row).max(1);
+
+        let mut merger = Self::unallocated(image_dimensions, images_per_row, total_lines);
+        merger.allocate_canvas();
+        merger
+    }
+
+    /// Constructs a new GrowableMerger that pastes into an existing canvas instead of allocating its own, so a buffer can be
+    /// reused across runs. The canvas is cleared, and holds as many rows as fit in its height. It grows as more images are
+    /// pushed onto it, like any other canvas.
+    ///
+    /// Setting any option that changes the layout of the canvas, such as `with_padding`, allocates a new canvas, so the
+    /// adopted buffer is only reused by a merger without them.
+    ///
+    /// # Arguments
+    /// * `canvas` - The buffer to paste into. Its width must be the width of a row of images, and its height a multiple of the
+    ///   image height. A canvas with a height of zero is grown to hold a single row, reusing its capacity.
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    ///
+    /// # Returns
+    /// * `Ok` - The merger pasting into the canvas.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` or either of the
+    ///   image dimensions is zero, or a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the dimensions of
+    ///   the canvas do not match a grid of the images, where `expected` is the closest canvas that would.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let buffer = image::RgbImage::new(500, 200);
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_canvas(buffer, (100, 100), 5).unwrap();
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 10]);
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 200));
+    ///
+    /// // The buffer can be taken back out and reused for the next run.
+    /// let buffer = merger.into_canvas().into_buffer();
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_canvas(buffer, (100, 100), 5).unwrap();
+    /// assert_eq!(merger.get_num_images(), 0);
+    /// ```
+    pub fn with_canvas(
+        canvas: ImageBuffer<P, Vec<P::Subpixel>>,
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+    ) -> Result<Self, MergeError> {
+        check_layout(image_dimensions, images_per_row)?;
+
+        let (width, height) = canvas.dimensions();
+        let total_lines = height.div_ceil(image_dimensions.1).max(1);
+        let mut merger = Self::unallocated(image_dimensions, images_per_row, total_lines);
+        let expected = merger.canvas_dimensions_for(total_lines)?;
+        if width != expected.0 || (height != 0 && height != expected.1) {
+            return Err(MergeError::DimensionMismatch {
+                expected,
+                got: (width, height),
+            });
+        }
+
+        let mut container = canvas.into_raw();
+        container.fill(Zero::zero());
+        container.resize(
+            expected.0 as usize * expected.1 as usize * <P as Pixel>::CHANNEL_COUNT as usize,
+            Zero::zero(),
+        );
+
+        // The buffer was only resized if the canvas had no rows, so it is always the right size.
+        merger.canvas = ImageCell::new(
+            Image::try_from_raw(expected.0, expected.1, container)
+                .unwrap_or_else(|err| panic!("Could not adopt the canvas: {}", err)),
+        );
+        Ok(merger)
+    }
+
+    /// Panics if a merger can not be laid out with the given image dimensions and number of images per row.
+    fn check_layout(image_dimensions: (u32, u32), images_per_row: u32) {
+        if let Err(err) = check_layout(image_dimensions, images_per_row) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Constructs a merger with the default options and an empty placeholder canvas, which must be replaced or allocated
+    /// before the merger is used.
+    fn unallocated(image_dimensions: (u32, u32), images_per_row: u32, total_lines: u32) -> Self {
+        Self {
+            canvas: ImageCell::new(Image::new(0, 0)),
+            image_dimensions,
+            num_images: 0,
+            images_per_line: images_per_row,
+            last_pasted_index: None,
+            holes: BTreeSet::new(),
+            total_lines,
+            fill_order: FillOrder::RowMajor,
+            fill_directions: Default::default(),
+            fixed_size: false,
+            growth_strategy: GrowthStrategy::SingleRow,
+            max_canvas_dimensions: None,
+            padding: None,
+            background: None,
+            border: None,
+            caption_height: 0,
+            tight_last_row: false,
+            header: None,
+            footer: None,
+            resize_filter: None,
+            preserve_aspect_ratio: false,
+            cell_alignment: None,
+            progress_callback: None,
+            memory_budget: None,
+            flush_callback: None,
+            flushed_rows: 0,
+        }
+    }
+
+    /// Constructs a new GrowableMerger with a fixed grid of `columns` by `rows` images. The canvas is allocated once at its
+    /// final size and never grows, pushing an image onto a full canvas returns a
+    /// [MergeError::OutOfCapacity](MergeError::OutOfCapacity) error instead.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `columns` - The number of images per row.
+    /// * `rows` - The number of rows.
+    ///
+    /// # Panics
+    /// This function will panic if `columns`, `rows` or either of the image dimensions is zero, or if the grid is too large.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, MergeError, Rgb, BufferedImage};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::fixed_grid((100, 100), 2, 1);
+    /// let image = BufferedImage::new(100, 100);
+    /// merger.bulk_push(&[&image, &image]);
+    ///
+    /// assert!(matches!(merger.try_push(&image), Err(MergeError::OutOfCapacity { capacity: 2 })));
+    /// assert_eq!(merger.get_canvas().dimensions(), (200, 100));
+    /// ```
+    pub fn fixed_grid(image_dimensions: (u32, u32), columns: u32, rows: u32) -> Self {
+        Self::try_fixed_grid(image_dimensions, columns, rows)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Constructs a new GrowableMerger with a fixed grid like `fixed_grid`, but returns an error instead of panicking if the
+    /// grid can not be laid out.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `columns` - The number of images per row.
+    /// * `rows` - The number of rows.
+    ///
+    /// # Returns
+    /// * `Ok` - The merger.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `columns`, `rows` or either of
+    ///   the image dimensions is zero, a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the grid holds more
+    ///   images than can be counted or the canvas would be too large to fit in memory, or a
+    ///   [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory for the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{GrowableMerger, MergeError, Rgb};
+    ///
+    /// let merger = GrowableMerger::<Rgb<u8>>::try_fixed_grid((1, 1), 65536, 65536);
+    /// assert!(matches!(merger, Err(MergeError::CanvasTooLarge { .. })));
+    /// ```
+    pub fn try_fixed_grid(
+        image_dimensions: (u32, u32),
+        columns: u32,
+        rows: u32,
+    ) -> Result<Self, MergeError> {
+        check_layout(image_dimensions, columns)?;
+        if rows == 0 {
+            return Err(MergeError::InvalidConfiguration(
+                "a fixed grid must have at least one row",
+            ));
+        }
+        // The capacity of the grid is counted in a u32, so the grid can not hold more images than that.
+        if columns.checked_mul(rows).is_none() {
+            return Err(MergeError::CanvasTooLarge {
+                max_dimensions: None,
+            });
+        }
+
+        let mut merger = Self::unallocated(image_dimensions, columns, rows);
+        merger.fixed_size = true;
+        merger.check_fits(rows)?;
+        merger.try_allocate_canvas()?;
+        Ok(merger)
+    }
+
+    /// Constructs a new GrowableMerger that holds the given images. The image dimensions of the merger are taken from the first
+    /// image, and the canvas is sized to fit every image exactly.
+    ///
+    /// # Arguments
+    /// * `images` - The images to merge. Every image must be the same size.
+    /// * `images_per_row` - The number of images per row.
+    ///
+    /// # Returns
+    /// * `Ok` - The merger holding the images.
+    /// * `Err` - A [MergeError::NoImages](MergeError::NoImages) if no images were given, a
+    ///   [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` or either of the dimensions
+    ///   of the images is zero, a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the images are not all
+    ///   the same size, a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if a canvas holding every image would be
+    ///   too large to address, or a [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough
+    ///   memory for it.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let image = BufferedImage::new(100, 100);
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::from_images(&[&image; 12], 5).unwrap();
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 300));
+    /// ```
+    pub fn from_images(
+        images: &[&BufferedImage<P>],
+        images_per_row: u32,
+    ) -> Result<Self, MergeError> {
+        let first = images.first().ok_or(MergeError::NoImages)?;
+        check_layout(first.dimensions(), images_per_row)?;
+        let num_images = u32::try_from(images.len()).map_err(|_| MergeError::CanvasTooLarge {
+            max_dimensions: None,
+        })?;
+
+        // The canvas always holds at least one row, like one made `with_capacity`.
+        let total_lines = num_images.div_ceil(images_per_row).max(1);
+        let mut merger = Self::unallocated(first.dimensions(), images_per_row, total_lines);
+        merger.check_fits(total_lines)?;
+        merger.try_allocate_canvas()?;
+        merger.try_bulk_push(images)?;
+        Ok(merger)
+    }
+
+    /// Calculates the dimensions of the canvas a merger would need to hold the given number of images, without allocating
+    /// anything. This matches the canvas of a merger constructed with `with_capacity` and the same arguments, without any
+    /// borders.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted.
+    /// * `images_per_row` - The number of images per row.
+    /// * `num_images` - The number of images on the canvas.
+    /// * `padding` - The padding between images, use `Padding { x: 0, y: 0 }` for none.
+    ///
+    /// # Returns
+    /// The dimensions, (x, y), of the canvas in pixels.
+    ///
+    /// # Panics
+    /// This function will panic if `images_per_row` is zero, or if the canvas would be too large for its dimensions to fit in
+    /// a u32.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{GrowableMerger, Padding, Rgb};
+    ///
+    /// let dimensions = GrowableMerger::<Rgb<u8>>::layout_dimensions((100, 100), 10, 95, Padding { x: 10, y: 5 });
+    /// assert_eq!(dimensions, (1090, 1045));
+    /// ```
+    pub fn layout_dimensions(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        num_images: u32,
+        padding: Padding,
+    ) -> (u32, u32) {
+        if images_per_row == 0 {
+            panic!("A GrowableMerger must have at least one image per row.");
+        }
+
+        let rows = num_images.div_ceil(images_per_row).max(1);
+        Self::checked_grid_dimensions(image_dimensions, images_per_row, rows, padding)
+            .unwrap_or_else(|| {
+                panic!(
+                    "{}",
+                    MergeError::CanvasTooLarge {
+                        max_dimensions: None
+                    }
+                )
+            })
+    }
+
+    /// Estimates how many bytes the canvas of a merger holding the given number of images would take up, without allocating
+    /// anything. This uses the canvas from `layout_dimensions`, and the real channel count and subpixel size of `P`.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted.
+    /// * `images_per_row` - The number of images per row.
+    /// * `num_images` - The number of images on the canvas.
+    /// * `padding` - The padding between images, use `Padding { x: 0, y: 0 }` for none.
+    ///
+    /// # Panics
+    /// This function will panic if `images_per_row` is zero, or if the canvas would be too large for its dimensions to fit in
+    /// a u32.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{GrowableMerger, Padding, Rgb};
+    ///
+    /// let bytes = GrowableMerger::<Rgb<u16>>::estimate_bytes_for((100, 100), 10, 95, Padding { x: 0, y: 0 });
+    /// assert_eq!(bytes, 1000 * 1000 * 3 * 2);
+    /// ```
+    pub fn estimate_bytes_for(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        num_images: u32,
+        padding: Padding,
+    ) -> usize {
+        let (width, height) =
+            Self::layout_dimensions(image_dimensions, images_per_row, num_images, padding);
+        Self::bytes_for(width, height)
+    }
+
+    /// Returns the number of bytes a canvas of the given dimensions takes up, or `usize::MAX` if that is too many to count.
+    fn bytes_for(width: u32, height: u32) -> usize {
+        (width as usize)
+            .saturating_mul(height as usize)
+            .saturating_mul(<P as Pixel>::CHANNEL_COUNT as usize)
+            .saturating_mul(std::mem::size_of::<P::Subpixel>())
+    }
+
+    /// Sets the padding between images on the canvas. The canvas is re-allocated to make room for the padding, so this
+    /// should be called right after constructing the merger.
+    ///
+    /// # Arguments
+    /// * `padding` - The padding between images.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Padding, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_padding(Padding { x: 10, y: 10 });
+    /// assert_eq!(merger.get_canvas().width(), 540);
+    /// ```
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        if self.num_images > 0 {
+            panic!("The padding can not be changed once images have been pushed onto the canvas.");
+        }
+
+        self.padding = Some(padding);
+        self.allocate_canvas();
+        self
+    }
+
+    /// Sets the background color of the canvas. Any space on the canvas that does not hold an image, including the padding
+    /// between images, is filled with this color. If no background is set, the canvas is filled with zeroes. The canvas is
+    /// re-allocated to apply the background, so this should be called right after constructing the merger.
+    ///
+    /// # Arguments
+    /// * `background` - The color to fill the canvas with.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_background(Rgb([255, 255, 255]));
+    /// assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgb([255, 255, 255]));
+    /// ```
+    pub fn with_background(self, background: P) -> Self {
+        self.with_background_pattern(Background::Solid(background))
+    }
+
+    /// Sets the background of the canvas, which can be a solid color or a checkerboard pattern. Any space on the canvas that
+    /// does not hold an image, including the padding between images, is filled with it, both when the canvas is allocated and
+    /// when it grows. A checkerboard is useful to see the transparent parts of the images. The squares of the checkerboard are
+    /// aligned to the top left corner of the canvas. The canvas is re-allocated to apply the background, so this should be
+    /// called right after constructing the merger.
+    ///
+    /// # Arguments
+    /// * `background` - The background to fill the canvas with.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas, or if the background is a checkerboard
+    /// with a square size of zero.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Background, Rgba};
+    ///
+    /// let (light, dark) = (Rgba([204, 204, 204, 255]), Rgba([153, 153, 153, 255]));
+    /// let merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 5)
+    ///     .with_background_pattern(Background::Checker { a: light, b: dark, size: 8 });
+    /// assert_eq!(merger.get_canvas().get_pixel(0, 0), &light);
+    /// assert_eq!(merger.get_canvas().get_pixel(8, 0), &dark);
+    /// assert_eq!(merger.get_canvas().get_pixel(8, 8), &light);
+    /// ```
+    pub fn with_background_pattern(mut self, background: Background<P>) -> Self {
+        if self.num_images > 0 {
+            panic!(
+                "The background can not be changed once images have been pushed onto the canvas."
+            );
+        }
+
+        self.background = Some(background);
+        self.check_background()
+            .unwrap_or_else(|err| panic!("{}", err));
+        self.allocate_canvas();
+        self
+    }
+
+    /// Draws a border around every place on the canvas that can hold an image. The border is drawn outside of the images, so
+    /// each place on the canvas grows by `thickness` pixels on every side, and any padding is added between the borders. Rows
+    /// that are added while the canvas grows get a border as well, and removing an image keeps its border. The canvas is
+    /// re-allocated to make room for the borders, so this should be called right after constructing the merger.
+    ///
+    /// # Arguments
+    /// * `thickness` - The thickness of the border in pixels.
+    /// * `color` - The color of the border.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_borders(2, Rgb([255, 255, 255]));
+    /// assert_eq!(merger.get_canvas().dimensions(), (520, 104));
+    /// assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgb([255, 255, 255]));
+    /// assert_eq!(merger.coordinates_of_index(1), (106, 2));
+    /// ```
+    pub fn with_borders(mut self, thickness: u32, color: P) -> Self {
+        if self.num_images > 0 {
+            panic!("The borders can not be changed once images have been pushed onto the canvas.");
+        }
+
+        self.border = Some((thickness, color));
+        self.allocate_canvas();
+        self
+    }
+
+    /// Reserves a strip of the given height below every place on the canvas for a caption, such as the file name of the
+    /// image. The strip is as wide as the place, including its border, and is filled with the background so it can be drawn
+    /// into later. Use `caption_coordinates_of_index` to find the strip of an image. The canvas is re-allocated to make room
+    /// for the strips, so this should be called right after constructing the merger.
+    ///
+    /// Captions belong to places rather than images, so they are not moved along with images that are removed or swapped.
+    ///
+    /// # Arguments
+    /// * `caption_height` - The height of the strip below each place.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 5, 10).with_caption_height(20);
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 240));
+    /// assert_eq!(merger.caption_coordinates_of_index(5), (0, 220));
+    /// ```
+    pub fn with_caption_height(mut self, caption_height: u32) -> Self {
+        if self.num_images > 0 {
+            panic!("The caption height can not be changed once images have been pushed onto the canvas.");
+        }
+
+        self.caption_height = caption_height;
+        self.allocate_canvas();
+        self
+    }
+
+    /// Adds a header band above the grid, such as a pre-rendered title or logo. The grid starts below the header, so no image
+    /// is ever pasted over it, and the header is redrawn whenever the canvas grows. The band is as tall as the header and spans
+    /// the whole canvas. The header is drawn from the left edge, the rest of the band is filled with the background, and a
+    /// header wider than the canvas is cut off at the right edge. The canvas is re-allocated to make room for the band, so
+    /// this should be called right after constructing the merger.
+    ///
+    /// # Arguments
+    /// * `header` - The image to draw above the grid.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 5, 10)
+    ///     .with_header(BufferedImage::new(500, 40))
+    ///     .with_footer(BufferedImage::new(500, 20));
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 260));
+    /// assert_eq!(merger.coordinates_of_index(5), (0, 140));
+    /// ```
+    pub fn with_header(mut self, header: BufferedImage<P>) -> Self {
+        if self.num_images > 0 {
+            panic!("The header can not be changed once images have been pushed onto the canvas.");
+        }
+
+        self.header = Some(header);
+        self.allocate_canvas();
+        self
+    }
+
+    /// Adds a footer band below the grid, like `with_header` does above it. The footer stays at the bottom of the canvas, and
+    /// moves down whenever the canvas grows.
+    ///
+    /// # Arguments
+    /// * `footer` - The image to draw below the grid.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    pub fn with_footer(mut self, footer: BufferedImage<P>) -> Self {
+        if self.num_images > 0 {
+            panic!("The footer can not be changed once images have been pushed onto the canvas.");
+        }
+
+        self.footer = Some(footer);
+        self.allocate_canvas();
+        self
+    }
+
+    /// Sets whether the copies of the canvas returned by `trim_transparent` crop the empty places at the end of the last row,
+    /// for a snug final image. The canvas is a rectangle, so the places can only be cropped when they are empty in every
+    /// row, which is when the last row is also the only row. When they can not be cropped, for example below a full row, they
+    /// are cleared to zero instead, along with their borders and the padding between them, which leaves them fully
+    /// transparent for pixels with an alpha channel. Only the empty places after the last image are cropped or cleared,
+    /// never the holes before it. In column major order, the empty places at the bottom of the last column are used
+    /// instead.
+    ///
+    /// The canvas itself keeps its full size, so more images can still be pushed onto it.
+    ///
+    /// # Arguments
+    /// * `tight_last_row` - Whether the empty places at the end of the last row are cropped or cleared.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_tight_last_row(true);
+    /// merger.bulk_push(&[&BufferedImage::new_from_pixel(100, 100, Rgb([255; 3])); 3]);
+    ///
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 100));
+    /// assert_eq!(merger.trim_transparent().dimensions(), (300, 100));
+    ///
+    /// // The second row keeps the full width, but its last two places are cleared.
+    /// merger.bulk_push(&[&BufferedImage::new_from_pixel(100, 100, Rgb([255; 3])); 5]);
+    /// let trimmed = merger.trim_transparent();
+    /// assert_eq!(trimmed.dimensions(), (500, 200));
+    /// assert_eq!(trimmed.get_pixel(350, 150), &Rgb([0; 3]));
+    /// ```
+    pub fn with_tight_last_row(mut self, tight_last_row: bool) -> Self {
+        self.tight_last_row = tight_last_row;
+        self
+    }
+
+    /// Sets the order that images are placed onto the canvas in. In [FillOrder::ColumnMajor](FillOrder::ColumnMajor) order,
+    /// the `images_per_row` the merger was constructed with is used as the number of images per column, and the canvas grows
+    /// column by column. The canvas is re-allocated to match the new order, so this should be called right after constructing
+    /// the merger.
+    ///
+    /// # Arguments
+    /// * `fill_order` - The order to place images in.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, FillOrder, Rgb};
+    ///
+    /// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_fill_order(FillOrder::ColumnMajor);
+    /// assert_eq!(merger.get_canvas().dimensions(), (100, 500));
+    /// ```
+    pub fn with_fill_order(mut self, fill_order: FillOrder) -> Self {
+        if self.num_images > 0 {
+            panic!(
+                "The fill order can not be changed once images have been pushed onto the canvas."
+            );
+        }
+
+        // A fixed grid keeps its number of columns and rows, so the length of each line has to be swapped with the number of
+        // lines when the direction of the lines changes.
+        if self.fixed_size && self.fill_order != fill_order {
+            std::mem::swap(&mut self.images_per_line, &mut self.total_lines);
+        }
+
+        self.fill_order = fill_order;
+        self.check_fill_order()
+            .unwrap_or_else(|err| panic!("{}", err));
+        self.allocate_canvas();
+        self
+    }
+
+    /// Sets the directions that images advance in along the rows and the columns of the canvas, which decides the corner the
+    /// first image is placed in. By default images are placed left to right and top to bottom, starting in the top left
+    /// corner. The directions work together with the fill order, so a row major merger that fills
+    /// [HorizontalDirection::RightToLeft](HorizontalDirection::RightToLeft) starts in the top right corner and fills each row
+    /// towards the left, like the pages of a manga.
+    ///
+    /// When the images fill towards the edge the canvas grows at, such as bottom to top in row major order, the images that
+    /// are already on the canvas have to be moved every time it grows or shrinks.
+    ///
+    /// # Arguments
+    /// * `horizontal` - The direction images advance in along each row.
+    /// * `vertical` - The direction images advance in along each column.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas, or if the merger has a memory budget
+    /// and the images would fill bottom to top.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, HorizontalDirection, VerticalDirection, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5)
+    ///     .with_fill_directions(HorizontalDirection::RightToLeft, VerticalDirection::TopToBottom);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 2]);
+    ///
+    /// assert_eq!(merger.coordinates_of_index(0), (400, 0));
+    /// assert_eq!(merger.coordinates_of_index(1), (300, 0));
+    /// ```
+    pub fn with_fill_directions(
+        mut self,
+        horizontal: HorizontalDirection,
+        vertical: VerticalDirection,
+    ) -> Self {
+        if self.num_images > 0 {
+            panic!(
+                "The fill directions can not be changed once images have been pushed onto the canvas."
+            );
+        }
+
+        self.fill_directions = (horizontal, vertical);
+        self.check_fill_order()
+            .unwrap_or_else(|err| panic!("{}", err));
+        self.allocate_canvas();
+        self
+    }
+
+    /// Returns the directions, (horizontal, vertical), that images advance in along the rows and the columns of the canvas.
+    pub fn get_fill_directions(&self) -> (HorizontalDirection, VerticalDirection) {
+        self.fill_directions
+    }
+
+    /// Limits how large the canvas is allowed to grow. Once growing the canvas would make it wider or taller than the given
+    /// dimensions, pushing an image returns a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) error instead of
+    /// allocating a larger canvas. This is useful as a safety valve when the images being pushed come from untrusted input.
+    ///
+    /// # Arguments
+    /// * `max_canvas_dimensions` - The maximum dimensions, (x, y), of the canvas in pixels.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, MergeError, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_max_canvas_dimensions((500, 200));
+    /// let image = BufferedImage::new(100, 100);
+    /// merger.bulk_push(&[&image; 10]);
+    ///
+    /// assert!(matches!(merger.try_push(&image), Err(MergeError::CanvasTooLarge { .. })));
+    /// ```
+    pub fn with_max_canvas_dimensions(mut self, max_canvas_dimensions: (u32, u32)) -> Self {
+        self.max_canvas_dimensions = Some(max_canvas_dimensions);
+        self
+    }
+
+    /// Caps how much memory the canvas may take up, so an unbounded stream of images can be merged with a fixed amount of
+    /// memory. When a push would need a canvas larger than the budget, every completed row is flushed off of the top of the
+    /// canvas: the rows are passed to `flush` as a single image, and the rows below them move up to reuse their space. If
+    /// the canvas is larger than the budget, it is shrunk to the most rows that fit.
+    ///
+    /// Flushed rows are gone for good, they can not be read, replaced or removed afterwards. Indices only refer to the images
+    /// that are still on the canvas, so after a flush the first image that was not flushed is at index 0, and
+    /// `get_num_images` only counts the images that are left. Use `get_num_flushed_rows` to find out how many rows came
+    /// before them. The first flushed image includes the header, and when the merger has padding, the flushed images are
+    /// `padding.y` apart from each other and from the rest of the canvas. Once every image has been pushed, the rows that
+    /// were not flushed are still on the canvas.
+    ///
+    /// A push that would not fit within the budget even after flushing returns a
+    /// [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) error, like one that would grow the canvas past its maximum
+    /// dimensions.
+    ///
+    /// # Arguments
+    /// * `bytes` - The most bytes the canvas may take up.
+    /// * `flush` - Called with the rows that are flushed off of the canvas, in order.
+    ///
+    /// # Panics
+    /// This function will panic if any images have already been pushed onto the canvas, if the merger fills in column major
+    /// order or bottom to top, or if the budget is too small to hold a single row of images.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let flushed = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&flushed);
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5)
+    ///     .with_memory_budget(500 * 200 * 3, move |rows| sink.lock().unwrap().push(rows.dimensions()));
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 10]);
+    /// merger.push(&BufferedImage::new(100, 100));
+    ///
+    /// // The two completed rows were flushed to make room for the third.
+    /// assert_eq!(*flushed.lock().unwrap(), vec![(500, 200)]);
+    /// assert_eq!(merger.get_num_flushed_rows(), 2);
+    /// assert_eq!(merger.get_num_images(), 1);
+    /// ```
+    pub fn with_memory_budget<F>(mut self, bytes: usize, flush: F) -> Self
+    where
+        F: FnMut(ImageBuffer<P, Vec<P::Subpixel>>) + Send + Sync + 'static,
+    {
+        if self.num_images > 0 {
+            panic!(
+                "The memory budget can not be set once images have been pushed onto the canvas."
+            );
+        }
+
+        self.memory_budget = Some(bytes);
+        self.flush_callback = Some(Box::new(flush));
+        self.check_fill_order()
+            .and_then(|_| self.check_memory_budget())
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        if !self.within_budget(self.total_lines) {
+            while !self.within_budget(self.total_lines) {
+                self.total_lines -= 1;
+            }
+            self.allocate_canvas();
+        }
+        self
+    }
+
+    /// Sets how much the canvas grows by when it is full and another image is pushed onto it. Growing by more than a single row
+    /// at a time means fewer reallocations when pushing many images one by one, at the cost of empty rows at the end of the
+    /// canvas, which can be removed with `shrink_to_fit`. The canvas never grows past its maximum dimensions, if set. Bulk
+    /// operations always grow the canvas to exactly the size they need.
+    ///
+    /// # Arguments
+    /// * `growth_strategy` - How many rows (or columns in column major order) to add when the canvas grows.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, GrowthStrategy, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_growth_strategy(GrowthStrategy::Double);
+    /// for _ in 0..11 {
+    ///     merger.push(&BufferedImage::new(100, 100));
+    /// }
+    ///
+    /// // The canvas grew from one row to two, and then to four, even though only three of them hold images.
+    /// assert_eq!(merger.get_canvas().height(), 400);
+    /// assert_eq!(merger.get_num_rows(), 3);
+    /// ```
+    pub fn with_growth_strategy(mut self, growth_strategy: GrowthStrategy) -> Self {
+        self.growth_strategy = growth_strategy;
+        self
+    }
+
+    /// Makes the merger resize any pushed image that is not the same size as the image dimensions of the merger, instead of
+    /// rejecting it. When the aspect ratio is preserved, the image is scaled to fit inside of its place on the canvas and
+    /// centered in it, or aligned as set with `with_cell_alignment`, with any remaining space filled with the background.
+    ///
+    /// # Arguments
+    /// * `filter` - The filter to resize images with.
+    /// * `preserve_aspect_ratio` - Whether to keep the aspect ratio of resized images.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    /// use image::imageops::FilterType;
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_resize_on_push(FilterType::Triangle, false);
+    /// merger.push(&BufferedImage::new(250, 400));
+    /// ```
+    pub fn with_resize_on_push(mut self, filter: FilterType, preserve_aspect_ratio: bool) -> Self {
+        self.resize_filter = Some(filter);
+        self.preserve_aspect_ratio = preserve_aspect_ratio;
+        self
+    }
+
+    /// Sets where images that are smaller than the image dimensions of the merger are positioned within their place on the
+    /// canvas, instead of rejecting them. The rest of the place is filled with the background. This also positions images that
+    /// are letterboxed by `with_resize_on_push` when the aspect ratio is preserved. Images that are larger than the image
+    /// dimensions of the merger are still rejected, unless they are resized on push.
+    ///
+    /// # Arguments
+    /// * `alignment` - Where to position undersized images within their place.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, CellAlignment, Rgb};
+    ///
+    /// let white = Rgb([255, 255, 255]);
+    /// let mut merger: GrowableMerger<Rgb<u8>> =
+    ///     GrowableMerger::new((100, 100), 5).with_cell_alignment(CellAlignment::BottomRight);
+    /// merger.push(&BufferedImage::new_from_pixel(40, 40, white));
+    ///
+    /// assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgb([0, 0, 0]));
+    /// assert_eq!(merger.get_canvas().get_pixel(60, 60), &white);
+    /// ```
+    pub fn with_cell_alignment(mut self, alignment: CellAlignment) -> Self {
+        self.cell_alignment = Some(alignment);
+        self
+    }
+
+    /// Sets a callback that is called as bulk operations, `bulk_push`, `try_bulk_push` and `push_dir`, make progress. The
+    /// callback is given the number of images that are done and the total number of images in the operation. Because
+    /// `bulk_push` pastes images in parallel, its progress is reported after each batch of images instead of after every
+    /// single one.
+    ///
+    /// # Arguments
+    /// * `callback` - The function to call with `(done, total)`.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.set_progress_callback(|done, total| println!("{}/{}", done, total));
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 12]);
+    /// ```
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(u32, u32) + Send + Sync + 'static,
+    {
+        self.progress_callback = Some(Box::new(callback));
+    }
+
+    /// Removes the progress callback set with `set_progress_callback`, if any.
+    pub fn remove_progress_callback(&mut self) {
+        self.progress_callback = None;
+    }
+
+    /// Resizes the given image to the image dimensions of the merger if it is the wrong size and the merger was set to resize
+    /// images on push, or places it within an image of the right size if it is undersized and the merger was given a cell
+    /// alignment. Returns `None` if the image can be pasted as-is, or can not be fitted. The background around a letterboxed
+    /// image lines up with the background of the canvas at the given index.
+    fn fit_image(&self, image: &BufferedImage<P>, index: u32) -> Option<BufferedImage<P>> {
+        if image.dimensions() == self.image_dimensions || !self.can_fit(image) {
+            return None;
+        }
+
+        let (width, height) = self.image_dimensions;
+        let resized = match self.resize_filter {
+            Some(filter) if !self.preserve_aspect_ratio => {
+                return Some(Image::from(image::imageops::resize(
+                    &**image, width, height, filter,
+                )));
+            }
+            Some(filter) => {
+                // Scale the image down (or up) until it fits inside of its place.
+                let scale = f64::min(
+                    width as f64 / image.width() as f64,
+                    height as f64 / image.height() as f64,
+                );
+                let scaled_width = ((image.width() as f64 * scale).round() as u32).clamp(1, width);
+                let scaled_height =
+                    ((image.height() as f64 * scale).round() as u32).clamp(1, height);
+                Some(image::imageops::resize(
+                    &**image,
+                    scaled_width,
+                    scaled_height,
+                    filter,
+                ))
+            }
+            None => None,
+        };
+        let placed = resized.as_ref().unwrap_or(image);
+
+        let (origin_x, origin_y) = self.get_paste_coordinates_unchecked(index);
+        let mut fitted: BufferedImage<P> = match self.background {
+            Some(background) => Image::from(ImageBuffer::from_fn(width, height, |x, y| {
+                background.pixel_at(origin_x + x, origin_y + y)
+            })),
+            None => Image::new(width, height),
+        };
+        let (offset_x, offset_y) = self
+            .cell_alignment
+            .unwrap_or(CellAlignment::Center)
+            .offset((width, height), placed.dimensions());
+        image::imageops::replace(&mut *fitted, placed, offset_x as i64, offset_y as i64);
+        Some(fitted)
+    }
+
+    /// Returns whether `fit_image` can fit the given image to the image dimensions of the merger, either by resizing it or by
+    /// placing it within its place.
+    fn can_fit(&self, image: &BufferedImage<P>) -> bool {
+        let (width, height) = self.image_dimensions;
+        self.resize_filter.is_some()
+            || (self.cell_alignment.is_some() && image.width() <= width && image.height() <= height)
+    }
+
+    /// Returns the number of (columns, rows) in a grid with the given number of lines.
+    fn grid_size(&self, lines: u32) -> (u32, u32) {
+        match self.fill_order {
+            FillOrder::RowMajor => (self.images_per_line, lines),
+            FillOrder::ColumnMajor => (lines, self.images_per_line),
+        }
+    }
+
+    /// Returns the dimensions, (x, y), in pixels of a canvas holding the given number of lines, or a
+    /// [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) error if the canvas would be too large to address.
+    fn canvas_dimensions_for(&self, lines: u32) -> Result<(u32, u32), MergeError> {
+        self.checked_canvas_dimensions_for(lines)
+            .ok_or(MergeError::CanvasTooLarge {
+                max_dimensions: None,
+            })
+    }
+
+    /// Returns the heights of the (header, footer) bands, zero for a band that is not set.
+    fn band_heights(&self) -> (u32, u32) {
+        let height =
+            |band: &Option<BufferedImage<P>>| band.as_ref().map_or(0, |band| band.height());
+        (height(&self.header), height(&self.footer))
+    }
+
+    /// Draws the header at the top of the canvas and the footer at the bottom, filling the rest of their bands with the
+    /// background.
+    fn draw_bands(&mut self) {
+        let (width, height) = self.canvas.dimensions();
+        let (header_height, footer_height) = self.band_heights();
+        let footer_y = height - footer_height;
+        self.fill_background(0, 0, width, header_height);
+        self.fill_background(0, footer_y, width, footer_height);
+
+        let canvas: &mut ImageBuffer<P, Vec<P::Subpixel>> = self.canvas.get_image_mut();
+        if let Some(header) = &self.header {
+            imageops::replace(canvas, &**header, 0, 0);
+        }
+        if let Some(footer) = &self.footer {
+            imageops::replace(canvas, &**footer, 0, footer_y as i64);
+        }
+    }
+
+    /// Returns the dimensions, (x, y), of each place on the canvas that holds an image, including its border and the caption
+    /// strip below it.
+    fn cell_dimensions(&self) -> (u32, u32) {
+        // The dimensions saturate instead of overflowing, so a cell that is too large is rejected by the checked canvas math.
+        let border = self.border_thickness().saturating_mul(2);
+        (
+            self.image_dimensions.0.saturating_add(border),
+            self.image_dimensions
+                .1
+                .saturating_add(border)
+                .saturating_add(self.caption_height),
+        )
+    }
+
+    #[inline(always)]
+    fn border_thickness(&self) -> u32 {
+        self.border
+            .as_ref()
+            .map(|(thickness, _)| *thickness)
+            .unwrap_or(0)
+    }
+
+    /// Returns the dimensions, (x, y), in pixels of a grid of `columns` by `rows` cells, or `None` if they do not fit in a u32.
+    fn checked_grid_dimensions(
+        image_dimensions: (u32, u32),
+        columns: u32,
+        rows: u32,
+        padding: Padding,
+    ) -> Option<(u32, u32)> {
+        let span = |cell: u32, cells: u32, padding: u32| {
+            cell.checked_mul(cells)?
+                .checked_add(cells.checked_sub(1)?.checked_mul(padding)?)
+        };
+        Some((
+            span(image_dimensions.0, columns, padding.x)?,
+            span(image_dimensions.1, rows, padding.y)?,
+        ))
+    }
+
+    /// Replaces the canvas with an empty one that is large enough to hold `total_lines` lines.
+    ///
+    /// # Panics
+    /// This function will panic if there is not enough memory for the canvas.
+    fn allocate_canvas(&mut self) {
+        if let Err(err) = self.try_allocate_canvas() {
+            panic!("{}", err);
+        }
+    }
+
+    /// Replaces the canvas with an empty one that is large enough to hold `total_lines` lines, returning a
+    /// [MergeError::AllocationFailed](MergeError::AllocationFailed) error instead of aborting if there is not enough memory
+    /// for it. The canvas is left as it is in that case.
+    fn try_allocate_canvas(&mut self) -> Result<(), MergeError> {
+        let (width, height) = self.canvas_dimensions_for(self.total_lines)?;
+
+        let mut container = Vec::new();
+        Self::try_reserve_canvas(&mut container, width, height)?;
+        container.resize(
+            width as usize * height as usize * <P as Pixel>::CHANNEL_COUNT as usize,
+            Zero::zero(),
+        );
+
+        // The buffer is always the right size, unless the canvas dimensions were miscalculated.
+        self.canvas = ImageCell::new(
+            Image::try_from_raw(width, height, container)
+                .unwrap_or_else(|err| panic!("Could not allocate the canvas: {}", err)),
+        );
+        if self.background.is_some() {
+            self.fill_background(0, 0, width, height);
+        }
+        self.draw_borders(0);
+        self.draw_bands();
+        Ok(())
+    }
+
+    /// Reserves enough space in the given buffer to hold the subpixels of a canvas of the given dimensions, using a fallible
+    /// allocation so running out of memory returns a [MergeError::AllocationFailed](MergeError::AllocationFailed) error
+    /// instead of aborting the process.
+    fn try_reserve_canvas(
+        container: &mut Vec<P::Subpixel>,
+        width: u32,
+        height: u32,
+    ) -> Result<(), MergeError> {
+        let len = width as usize * height as usize * <P as Pixel>::CHANNEL_COUNT as usize;
+        container
+            .try_reserve_exact(len.saturating_sub(container.len()))
+            .map_err(|_| MergeError::AllocationFailed {
+                bytes: Self::bytes_for(width, height),
+            })
+    }
+
+    /// Draws the border around every place on the canvas, starting at the given line.
+    fn draw_borders(&mut self, from_line: u32) {
+        let Some((thickness, color)) = self.border else {
+            return;
+        };
+        if thickness == 0 {
+            return;
+        }
+
+        // The border goes around the image, not around the caption strip below it.
+        let (image_width, image_height) = self.image_dimensions;
+        let (cell_width, cell_height) = (image_width + 2 * thickness, image_height + 2 * thickness);
+        let first_index = from_line * self.images_per_line;
+        let last_index = self.total_lines * self.images_per_line;
+
+        for index in first_index..last_index {
+            let (x, y) = self.get_paste_coordinates_unchecked(index);
+            let (left, top) = (x - thickness, y - thickness);
+
+            let canvas: &mut ImageBuffer<P, Vec<P::Subpixel>> = self.canvas.get_image_mut();
+            for offset_y in 0..cell_height {
+                let is_edge = offset_y < thickness || offset_y >= thickness + image_height;
+                for offset_x in 0..cell_width {
+                    if is_edge || offset_x < thickness || offset_x >= thickness + image_width {
+                        canvas.put_pixel(left + offset_x, top + offset_y, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills the given rectangle of the canvas with the background, or with zeroes if no background is set.
+    fn fill_background(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        let background = self.background;
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let canvas_width = self.canvas.width() as usize;
+        let row_len = width as usize * channels;
+
+        let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+        for row in y..y + height {
+            let start = (row as usize * canvas_width + x as usize) * channels;
+            let pixels = &mut buffer[start..start + row_len];
+            match background {
+                Some(background) => {
+                    pixels
+                        .chunks_exact_mut(channels)
+                        .zip(x..)
+                        .for_each(|(chunk, column)| {
+                            chunk.copy_from_slice(background.pixel_at(column, row).channels())
+                        })
+                }
+                None => pixels.fill(Zero::zero()),
+            }
+        }
+    }
+
+    /// Returns the number of images that have been pasted to the canvas.
+    pub fn get_num_images(&self) -> u32 {
+        self.num_images
+    }
+
+    /// Returns the index of the most recently pasted image, or `None` if no images have been pasted.
+    pub fn last_pasted_index(&self) -> Option<u32> {
+        self.last_pasted_index
+    }
+
+    /// Returns the number of places currently allocated on the canvas, whether they hold an image or not. The canvas grows
+    /// once this many images have been pushed onto it.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 5, 8);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 3]);
+    /// assert_eq!(merger.capacity(), 10);
+    /// assert_eq!(merger.remaining_capacity(), 7);
+    /// ```
+    pub fn capacity(&self) -> u32 {
+        self.images_per_line.saturating_mul(self.total_lines)
+    }
+
+    /// Returns the number of images that can still be pushed onto the canvas before it has to grow.
+    pub fn remaining_capacity(&self) -> u32 {
+        self.capacity().saturating_sub(self.num_images)
+    }
+
+    /// Returns the dimensions, (x, y), of the images being pasted to the canvas.
+    pub fn get_image_dimensions(&self) -> (u32, u32) {
+        self.image_dimensions
+    }
+
+    /// Returns the number of rows that have been flushed off of the canvas by the memory budget set with
+    /// `with_memory_budget`.
+    pub fn get_num_flushed_rows(&self) -> u32 {
+        self.flushed_rows
+    }
+
+    /// Returns the number of rows that hold at least one image. This does not include any empty rows that have been
+    /// allocated ahead of time. When filling in column major order, this is the number of images per column.
+    pub fn get_num_rows(&self) -> u32 {
+        self.grid_size(self.num_images.div_ceil(self.images_per_line))
+            .1
+    }
+
+    /// Returns the number of columns on the canvas, which is the number of images per row. When filling in column major
+    /// order, this is the number of columns that hold at least one image instead.
+    pub fn get_num_columns(&self) -> u32 {
+        self.grid_size(self.num_images.div_ceil(self.images_per_line))
+            .0
+    }
+
+    /// Returns the dimensions, (x, y), of the canvas in pixels.
+    pub fn get_canvas_dimensions(&self) -> (u32, u32) {
+        self.canvas.dimensions()
+    }
+
+    /// Returns the number of bytes the pixels of the canvas currently take up. This does not include any spare capacity of the
+    /// underlying buffer.
+    pub fn estimated_bytes(&self) -> usize {
+        let (width, height) = self.canvas.dimensions();
+        Self::bytes_for(width, height)
+    }
+
+    /// Returns the coordinates, (x, y), of the top left pixel of the image at the given index. The index does not need to hold
+    /// an image yet, so this can be used to find out where a future image will be pasted.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image.
+    pub fn coordinates_of_index(&self, index: u32) -> (u32, u32) {
+        self.get_paste_coordinates_unchecked(index)
+    }
+
+    /// Returns an iterator over the rectangle of every occupied place on the canvas, in index order. Each item is the index of
+    /// the place and its (x, y, width, height) in pixels, found with the same layout math as `coordinates_of_index`. The
+    /// rectangle covers the image, not its border or caption strip. Holes are skipped, like every place `is_cell_occupied`
+    /// returns false for. This is useful for drawing annotations onto the canvas with another library.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Padding, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5).with_padding(Padding { x: 10, y: 10 });
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 7]);
+    ///
+    /// let rectangles: Vec<_> = merger.iter_coordinates().collect();
+    /// assert_eq!(rectangles.len(), 7);
+    /// assert_eq!(rectangles[6], (6, (110, 110, 100, 100)));
+    /// ```
+    pub fn iter_coordinates(&self) -> impl Iterator<Item = (u32, (u32, u32, u32, u32))> + '_ {
+        self.occupied_indices()
+            .map(move |index| (index, self.cell_rect(index)))
+    }
+
+    /// Returns the coordinates, (x, y), of the top left pixel of the caption strip below the image at the given index. The
+    /// strip is as wide as the place of the image, including its border, and as tall as the caption height set with
+    /// `with_caption_height`.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image.
+    pub fn caption_coordinates_of_index(&self, index: u32) -> (u32, u32) {
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        let thickness = self.border_thickness();
+        (x - thickness, y + self.image_dimensions.1 + thickness)
+    }
+
+    /// Returns the index of the image that the pixel at the given coordinates belongs to.
+    ///
+    /// # Arguments
+    /// * `x` - The x coordinate of the pixel.
+    /// * `y` - The y coordinate of the pixel.
+    ///
+    /// # Returns
+    /// * `Some` - The index of the image the pixel belongs to.
+    /// * `None` - If the pixel is in the padding between images, or is not part of any pasted image.
+    pub fn index_at_pixel(&self, x: u32, y: u32) -> Option<u32> {
+        let y = y.checked_sub(self.band_heights().0)?;
+        let (cell_width, cell_height) = self.cell_dimensions();
+        let pitch_x = cell_width + self.padding.as_ref().map(|p| p.x).unwrap_or(0);
+        let pitch_y = cell_height + self.padding.as_ref().map(|p| p.y).unwrap_or(0);
+        let thickness = self.border_thickness();
+
+        let offset_x = x / pitch_x;
+        let offset_y = y / pitch_y;
+        let (columns, rows) = self.grid_size(self.total_lines);
+        if offset_x >= columns
+            || offset_y >= rows
+            || !(thickness..thickness + self.image_dimensions.0).contains(&(x % pitch_x))
+            || !(thickness..thickness + self.image_dimensions.1).contains(&(y % pitch_y))
+        {
+            return None;
+        }
+        let (offset_x, offset_y) = self.mirror_offsets(offset_x, offset_y, (columns, rows));
+
+        let index = match self.fill_order {
+            FillOrder::RowMajor => offset_y * self.images_per_line + offset_x,
+            FillOrder::ColumnMajor => offset_x * self.images_per_line + offset_y,
+        };
+        (index < self.num_images).then_some(index)
+    }
+
+    /// Returns true if an image has been pasted at the given index. Places that have been allocated on the canvas but not
+    /// pasted to yet, such as the spare places of a merger made `with_capacity`, are not occupied, and neither are the holes
+    /// left by `push_at` or by removing an image with [RemovalMode::Leave](RemovalMode::Leave).
+    ///
+    /// # Arguments
+    /// * `index` - The index of the place.
+    pub fn is_cell_occupied(&self, index: u32) -> bool {
+        index < self.num_images && !self.holes.contains(&index)
+    }
+
+    /// Returns the range of indices up to the last image. Every index after it is empty, and every index in it is occupied
+    /// unless it is a hole, which `compact` removes.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 5, 10);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 3]);
+    /// assert_eq!(merger.occupied_cells(), 0..3);
+    /// assert!(merger.is_cell_occupied(2));
+    /// assert!(!merger.is_cell_occupied(3));
+    /// ```
+    pub fn occupied_cells(&self) -> Range<u32> {
+        0..self.num_images
+    }
+
+    /// Returns an iterator over the index of every place that holds an image, in order. Every method that goes through the
+    /// images on the canvas uses this, so they all skip the same holes as `is_cell_occupied`.
+    fn occupied_indices(&self) -> impl Iterator<Item = u32> + '_ {
+        self.occupied_cells()
+            .filter(move |&index| self.is_cell_occupied(index))
+    }
+
+    /// Returns true if the canvas has no free places left, so the next push will have to grow it. A fixed grid never grows,
+    /// so this always returns false for one.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 2);
+    /// merger.push(&BufferedImage::new(100, 100));
+    /// assert!(!merger.will_grow_on_next_push());
+    /// merger.push(&BufferedImage::new(100, 100));
+    /// assert!(merger.will_grow_on_next_push());
+    /// ```
+    pub fn will_grow_on_next_push(&self) -> bool {
+        !self.fixed_size && self.is_full()
+    }
+
+    /// Returns true if every place on the canvas has been pasted to. This is a comparison rather than a subtraction so that the
+    /// bookkeeping can never underflow.
+    #[inline(always)]
+    fn is_full(&self) -> bool {
+        self.num_images >= self.capacity()
+    }
+
+    /// Returns the index the next image is pasted at.
+    fn next_index(&self) -> u32 {
+        self.last_pasted_index.map_or(0, |index| index + 1)
+    }
+
+    /// Returns whether an image pasted at the given coordinates lies completely on the canvas.
+    fn fits_on_canvas(&self, x: u32, y: u32) -> bool {
+        let (width, height) = self.image_dimensions;
+        x.checked_add(width)
+            .is_some_and(|right| right <= self.canvas.width())
+            && y.checked_add(height)
+                .is_some_and(|bottom| bottom <= self.canvas.height())
+    }
+
+    /// Makes sure there is space on the canvas for the next image, growing the canvas if it is full, and returns the coordinates
+    /// the next image is pasted at. The place is checked against the actual size of the canvas, so an image is never pasted
+    /// partly off of its edge.
+    fn next_place(&mut self) -> Result<(u32, u32), MergeError> {
+        if self.is_full() {
+            self.flush_to_fit(self.total_lines + 1);
+        }
+        if self.is_full() {
+            self.check_can_grow(self.total_lines + 1)?;
+            self.grow_canvas()?;
+        }
+
+        self.place_of(self.next_index())
+    }
+
+    /// Makes sure the place at the given index lies on the canvas, growing the canvas to include its row (or column in column
+    /// major order) if needed, and returns the coordinates an image at that index is pasted at.
+    fn place_of(&mut self, index: u32) -> Result<(u32, u32), MergeError> {
+        // The canvas is checked first, so the coordinates of an index far past its end can never overflow.
+        let lines = self.total_lines.max(index / self.images_per_line + 1);
+        if lines > self.total_lines {
+            self.check_can_grow(lines)?;
+        }
+
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        if !self.fits_on_canvas(x, y) {
+            // The canvas does not match the layout, so it is resized to the size the layout needs.
+            self.resize_canvas(lines)?;
+        }
+
+        debug_assert!(
+            self.fits_on_canvas(x, y),
+            "The image at index {} does not fit on the canvas.",
+            index
+        );
+        Ok((x, y))
+    }
+
+    /// Returns the (x, y, width, height) in pixels of the empty places after the last image in its line, including their
+    /// borders and the padding between them, or `None` if there are no images or the line is full.
+    fn trailing_places_rect(&self) -> Option<(u32, u32, u32, u32)> {
+        let first = self.num_images;
+        if first == 0 || first.is_multiple_of(self.images_per_line) {
+            return None;
+        }
+        let last = first - first % self.images_per_line + self.images_per_line - 1;
+
+        // The places may run in either direction depending on the fill directions, so the rectangle spans both ends.
+        let (cell_width, cell_height) = self.cell_dimensions();
+        let thickness = self.border_thickness();
+        let (first_x, first_y) = self.get_paste_coordinates_unchecked(first);
+        let (last_x, last_y) = self.get_paste_coordinates_unchecked(last);
+        let (x, y) = (
+            first_x.min(last_x) - thickness,
+            first_y.min(last_y) - thickness,
+        );
+        let width = first_x.max(last_x) - thickness + cell_width - x;
+        let height = first_y.max(last_y) - thickness + cell_height - y;
+        Some((x, y, width, height))
+    }
+
+    /// Returns the (x, y, width, height) in pixels of the image at the given index. Every method that reads, writes or clears
+    /// the pixels of a place goes through this, so they always agree on where the place is.
+    fn cell_rect(&self, index: u32) -> (u32, u32, u32, u32) {
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        let (width, height) = self.image_dimensions;
+        (x, y, width, height)
+    }
+
+    fn get_paste_coordinates_unchecked(&self, index: u32) -> (u32, u32) {
+        let (offset_x, offset_y) = match self.fill_order {
+            FillOrder::RowMajor => (index % self.images_per_line, index / self.images_per_line),
+            FillOrder::ColumnMajor => (index / self.images_per_line, index % self.images_per_line),
+        };
+        // A place past the end of the canvas is found on the grid the canvas would grow to for it.
+        let lines = self
+            .total_lines
+            .max((index / self.images_per_line).saturating_add(1));
+        let (offset_x, offset_y) = self.mirror_offsets(offset_x, offset_y, self.grid_size(lines));
+
+        let padding_x = self.padding.as_ref().map(|p| p.x).unwrap_or(0) * offset_x;
+        let padding_y = self.padding.as_ref().map(|p| p.y).unwrap_or(0) * offset_y;
+
+        let (cell_width, cell_height) = self.cell_dimensions();
+        let thickness = self.border_thickness();
+
+        let x = (offset_x * cell_width) + padding_x + thickness;
+        let y = (offset_y * cell_height) + padding_y + thickness + self.band_heights().0;
+
+        (x, y)
+    }
+
+    /// Mirrors the (column, row) of a place on a grid of the given (columns, rows) along the axes that are filled right to
+    /// left or bottom to top. Mirroring twice gives back the place it started at.
+    fn mirror_offsets(
+        &self,
+        offset_x: u32,
+        offset_y: u32,
+        (columns, rows): (u32, u32),
+    ) -> (u32, u32) {
+        let offset_x = match self.fill_directions.0 {
+            HorizontalDirection::LeftToRight => offset_x,
+            HorizontalDirection::RightToLeft => columns - 1 - offset_x,
+        };
+        let offset_y = match self.fill_directions.1 {
+            VerticalDirection::TopToBottom => offset_y,
+            VerticalDirection::BottomToTop => rows - 1 - offset_y,
+        };
+        (offset_x, offset_y)
+    }
+
+    /// Returns whether the images fill towards the edge the canvas grows at, so their places move whenever the number of
+    /// lines changes.
+    fn fills_towards_growth(&self) -> bool {
+        match self.fill_order {
+            FillOrder::RowMajor => self.fill_directions.1 == VerticalDirection::BottomToTop,
+            FillOrder::ColumnMajor => self.fill_directions.0 == HorizontalDirection::RightToLeft,
+        }
+    }
+
+    /// Flushes the completed rows off of the canvas if a canvas holding the given number of lines would take up more memory
+    /// than the memory budget allows.
+    fn flush_to_fit(&mut self, lines: u32) {
+        if self.rows_to_flush(lines) > 0 {
+            self.flush_completed_rows();
+        }
+    }
+
+    /// Returns the number of rows `flush_to_fit` would flush off of the canvas to make room for the given number of lines,
+    /// without flushing them.
+    fn rows_to_flush(&self, lines: u32) -> u32 {
+        if lines > self.total_lines && !self.within_budget(lines) && self.flush_callback.is_some() {
+            self.next_index() / self.images_per_line
+        } else {
+            0
+        }
+    }
+
+    /// Passes every completed row to the flush callback and moves the rows below them up to the top of the canvas, clearing
+    /// the space they leave behind. The header is flushed along with the first rows. Returns the number of rows that were
+    /// flushed.
+    fn flush_completed_rows(&mut self) -> u32 {
+        let rows = self.next_index() / self.images_per_line;
+        if rows == 0 {
+            return 0;
+        }
+        let Some(mut callback) = self.flush_callback.take() else {
+            return 0;
+        };
+
+        let (width, height) = self.canvas.dimensions();
+        let (header_height, footer_height) = self.band_heights();
+        let padding_y = self.padding.map_or(0, |padding| padding.y);
+        let line_height = self.cell_dimensions().1 + padding_y;
+        let row_len = width as usize * <P as Pixel>::CHANNEL_COUNT as usize;
+
+        // The flushed rows end at the bottom of the last one, the padding below it belongs to neither side.
+        let flushed_height = header_height + rows * line_height - padding_y;
+        let kept_start = (header_height + rows * line_height).min(height - footer_height);
+        let grid_end = height - footer_height;
+
+        let mut container = std::mem::take(&mut **self.canvas.get_image_mut()).into_raw();
+        let flushed = container[..flushed_height as usize * row_len].to_vec();
+        container.copy_within(
+            kept_start as usize * row_len..grid_end as usize * row_len,
+            0,
+        );
+
+        // The header has been flushed, so the canvas loses its band and the footer moves up.
+        self.header = None;
+        let new_height = height - header_height;
+        container.truncate(new_height as usize * row_len);
+        *self.canvas.get_image_mut() = Image::try_from_raw(width, new_height, container)
+            .unwrap_or_else(|err| panic!("Could not flush the canvas: {}", err));
+
+        let kept_lines = self.total_lines - rows;
+        let freed_y = kept_lines * line_height;
+        self.fill_background(0, freed_y, width, new_height - freed_y);
+        self.draw_borders(kept_lines);
+        self.draw_bands();
+
+        // Can always unwrap here because the buffer is always the right size.
+        callback(ImageBuffer::from_raw(width, flushed_height, flushed).unwrap());
+        self.flush_callback = Some(callback);
+        log_event!(debug, "Flushed {} rows off of the canvas.", rows);
+
+        let flushed_images = rows * self.images_per_line;
+        self.holes = self
+            .holes
+            .iter()
+            .filter_map(|hole| hole.checked_sub(flushed_images))
+            .collect();
+        self.num_images -= flushed_images;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        self.flushed_rows += rows;
+        rows
+    }
+
+    /// Grows the canvas by as many rows (or columns in column major order) as the growth strategy calls for. If growing by that
+    /// much would make the canvas larger than it is allowed to be, it grows by a single line instead, which the caller must have
+    /// checked is allowed.
+    fn grow_canvas(&mut self) -> Result<(), MergeError> {
+        let lines = self.growth_strategy.rows_to_add(self.total_lines);
+        let allowed = self
+            .total_lines
+            .checked_add(lines)
+            .is_some_and(|total_lines| self.check_can_grow(total_lines).is_ok());
+        let lines = if allowed { lines } else { 1 };
+        log_event!(debug, "The canvas is full, growing it by {} lines.", lines);
+        self.grow_canvas_by(lines)
+    }
+
+    /// Grows the canvas by the given number of rows (or columns in column major order) with a single allocation.
+    fn grow_canvas_by(&mut self, lines: u32) -> Result<(), MergeError> {
+        self.resize_canvas(self.total_lines + lines)
+    }
+
+    /// Resizes the canvas so it holds the given number of rows (or columns in column major order). New space is filled with
+    /// the background. If there is not enough memory for the resized canvas, a
+    /// [MergeError::AllocationFailed](MergeError::AllocationFailed) error is returned and the canvas is left as it is.
+    fn resize_canvas(&mut self, total_lines: u32) -> Result<(), MergeError> {
+        if self.fills_towards_growth() && total_lines != self.total_lines {
+            return self.relocate_canvas(total_lines);
+        }
+
+        let (width, height) = self.canvas.dimensions();
+        let (new_width, new_height) = self.canvas_dimensions_for(total_lines)?;
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+
+        let container = if new_width == width {
+            // Rows are stored contiguously, so the underlying buffer is resized in place and the pixel data of the rows that
+            // are kept is left untouched.
+            let mut container = std::mem::take(&mut **self.canvas.get_image_mut()).into_raw();
+            if let Err(err) = Self::try_reserve_canvas(&mut container, new_width, new_height) {
+                // The buffer was not touched, so it is put back as it was.
+                *self.canvas.get_image_mut() = Image::try_from_raw(width, height, container)
+                    .unwrap_or_else(|err| panic!("Could not resize the canvas: {}", err));
+                return Err(err);
+            }
+            container.resize(
+                width as usize * new_height as usize * channels,
+                Zero::zero(),
+            );
+            container
+        } else {
+            // The length of each row changes, so every row has to be copied over to a new buffer.
+            let mut container = Vec::new();
+            Self::try_reserve_canvas(&mut container, new_width, new_height)?;
+            container.resize(
+                new_width as usize * new_height as usize * channels,
+                Zero::zero(),
+            );
+            let old_container: &[P::Subpixel] = &self.canvas;
+
+            let kept_len = width.min(new_width) as usize * channels;
+            for row in 0..height.min(new_height) as usize {
+                let src = row * width as usize * channels;
+                let dst = row * new_width as usize * channels;
+                container[dst..dst + kept_len].copy_from_slice(&old_container[src..src + kept_len]);
+            }
+            container
+        };
+
+        // The buffer is always the right size, unless the canvas dimensions were miscalculated.
+        *self.canvas.get_image_mut() = Image::try_from_raw(new_width, new_height, container)
+            .unwrap_or_else(|err| panic!("Could not resize the canvas: {}", err));
+
+        // The parts of the canvas that were added are still zeroed, so they are filled with the background.
+        if self.background.is_some() {
+            if new_width > width {
+                self.fill_background(width, 0, new_width - width, height.min(new_height));
+            }
+            if new_height > height {
+                self.fill_background(0, height, new_width, new_height - height);
+            }
+        }
+
+        // The footer moves to the new bottom of the canvas, and its old band becomes part of the grid.
+        let footer_height = self.band_heights().1;
+        let old_footer_y = (height - footer_height).min(new_height);
+        if footer_height > 0 {
+            self.fill_background(
+                0,
+                old_footer_y,
+                new_width,
+                height.min(new_height) - old_footer_y,
+            );
+        }
+
+        let old_lines = self.total_lines;
+        log_event!(
+            debug,
+            "{} the canvas from {}x{} to {}x{}, going from {} to {} lines.",
+            match total_lines.cmp(&old_lines) {
+                std::cmp::Ordering::Greater => "Grew",
+                std::cmp::Ordering::Less => "Shrank",
+                std::cmp::Ordering::Equal => "Re-laid out",
+            },
+            width,
+            height,
+            new_width,
+            new_height,
+            old_lines,
+            total_lines
+        );
+        self.total_lines = total_lines;
+        if total_lines > old_lines {
+            self.draw_borders(old_lines);
+        }
+        self.draw_bands();
+        Ok(())
+    }
+
+    /// Copies the pixels of the image at index `from` onto the image at index `to`, row by row.
+    fn copy_image(&mut self, from: u32, to: u32) {
+        let (from_x, from_y, width, height) = self.cell_rect(from);
+        let (to_x, to_y, _, _) = self.cell_rect(to);
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let canvas_width = self.canvas.width() as usize;
+        let row_len = width as usize * channels;
+
+        let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+        for row in 0..height as usize {
+            let src = ((from_y as usize + row) * canvas_width + from_x as usize) * channels;
+            let dst = ((to_y as usize + row) * canvas_width + to_x as usize) * channels;
+            buffer.copy_within(src..src + row_len, dst);
+        }
+    }
+
+    /// Clears the image at the given index, setting every pixel in it to the background color.
+    fn clear_image(&mut self, index: u32) {
+        let (x, y, width, height) = self.cell_rect(index);
+        self.fill_background(x, y, width, height);
+    }
+
+    /// Returns a copy of the image at the given index. Indexing starts at 0 and works left to right, top to bottom.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image to copy.
+    ///
+    /// # Returns
+    /// * `Some` - A copy of the image at the given index.
+    /// * `None` - If there is no image at the given index, including if the index is a hole.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// let image = BufferedImage::new_from_pixel(100, 100, Rgb([255, 0, 0]));
+    /// merger.push(&image);
+    ///
+    /// assert_eq!(merger.get_image_at(0), Some(image));
+    /// assert_eq!(merger.get_image_at(1), None);
+    /// ```
+    pub fn get_image_at(&self, index: u32) -> Option<BufferedImage<P>> {
+        if !self.is_cell_occupied(index) {
+            return None;
+        }
+
+        Some(self.copy_cell(index))
+    }
+
+    /// Returns a copy of the place at the given index, whether or not it holds an image.
+    fn copy_cell(&self, index: u32) -> BufferedImage<P> {
+        let (x, y, width, height) = self.cell_rect(index);
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let canvas_width = self.canvas.width() as usize;
+        let row_len = width as usize * channels;
+
+        let buffer: &[P::Subpixel] = &self.canvas;
+        let mut container = Vec::with_capacity(row_len * height as usize);
+        for row in 0..height as usize {
+            let start = ((y as usize + row) * canvas_width + x as usize) * channels;
+            container.extend_from_slice(&buffer[start..start + row_len]);
+        }
+
+        // The buffer is always the right size, because it was filled row by row above.
+        Image::new_from_raw(width, height, container).unwrap()
+    }
+
+    /// Returns an iterator over every image on the canvas in the order they were pushed. Each item is the index of the image
+    /// and a view into the canvas covering it, so no pixel data is copied. Holes are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    /// use image::GenericImageView;
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new_from_pixel(100, 100, Rgb([255, 0, 0])); 3]);
+    ///
+    /// for (index, tile) in merger.tiles() {
+    ///     assert!(index < 3);
+    ///     assert_eq!(tile.get_pixel(50, 50), Rgb([255, 0, 0]));
+    /// }
+    /// ```
+    pub fn tiles(&self) -> impl Iterator<Item = (u32, Tile<'_, P>)> {
+        let canvas: &ImageBuffer<P, Vec<P::Subpixel>> = &self.canvas;
+
+        self.occupied_indices().map(move |index| {
+            let (x, y, width, height) = self.cell_rect(index);
+            (index, imageops::crop_imm(canvas, x, y, width, height))
+        })
+    }
+
+    /// Returns an iterator over every row of the grid that holds at least one image, top to bottom. Each item is a copy of the
+    /// row as its own image, as wide as the canvas and as tall as the images, so it does not include their borders, caption
+    /// strips or the padding between rows. This is useful for encoding or analysing the canvas one row at a time.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 5, 20);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 7]);
+    ///
+    /// let rows: Vec<_> = merger.rows().collect();
+    /// assert_eq!(rows.len(), 2);
+    /// assert_eq!(rows[1].dimensions(), (500, 100));
+    /// ```
+    pub fn rows(&self) -> impl Iterator<Item = ImageBuffer<P, Vec<P::Subpixel>>> + '_ {
+        // In column major order, the first column holds an image in every row that holds one at all.
+        let (rows, first_index_of): (u32, fn(u32, u32) -> u32) = match self.fill_order {
+            FillOrder::RowMajor => (
+                self.num_images.div_ceil(self.images_per_line),
+                |row, images_per_line| row * images_per_line,
+            ),
+            FillOrder::ColumnMajor => (self.num_images.min(self.images_per_line), |row, _| row),
+        };
+        let width = self.canvas.width();
+        let row_len = width as usize * <P as Pixel>::CHANNEL_COUNT as usize;
+
+        (0..rows).map(move |row| {
+            let (_, y, _, height) = self.cell_rect(first_index_of(row, self.images_per_line));
+            let buffer: &[P::Subpixel] = &self.canvas;
+            let start = y as usize * row_len;
+            let container = buffer[start..start + height as usize * row_len].to_vec();
+
+            // Can always unwrap here because the buffer is always the right size.
+            ImageBuffer::from_raw(width, height, container).unwrap()
+        })
+    }
+
+    /// Returns the raw subpixels of a row of places on the canvas, without copying them. The rows of pixels of the canvas are
+    /// stored one after another, so a row of places is a single contiguous slice, spanning the full width of the canvas and
+    /// the full height of the places, including their borders and caption strips but not the padding below them. This can be
+    /// used to hash or encode each row as soon as it is complete.
+    ///
+    /// This only works for the row major layout, in column major order the images of a row are not stored together.
+    ///
+    /// # Arguments
+    /// * `row` - The index of the row, starting at 0.
+    ///
+    /// # Returns
+    /// * `Some` - The subpixels of the row.
+    /// * `None` - If the row does not hold any images, or if the merger fills in column major order.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 5, 15);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 7]);
+    ///
+    /// assert_eq!(merger.row_slice(1).unwrap().len(), 500 * 100 * 3);
+    /// assert!(merger.row_slice(2).is_none());
+    /// ```
+    pub fn row_slice(&self, row: u32) -> Option<&[P::Subpixel]> {
+        if self.fill_order == FillOrder::ColumnMajor || row >= self.get_num_rows() {
+            return None;
+        }
+
+        let (_, y) = self.get_paste_coordinates_unchecked(row * self.images_per_line);
+        let top = (y - self.border_thickness()) as usize;
+        let height = self.cell_dimensions().1 as usize;
+        let row_len = self.canvas.width() as usize * <P as Pixel>::CHANNEL_COUNT as usize;
+
+        let buffer: &[P::Subpixel] = &self.canvas;
+        Some(&buffer[top * row_len..(top + height) * row_len])
+    }
+
+    /// Returns a copy of a block of places on the canvas, given by ranges of rows and columns of the grid. The block includes
+    /// the padding and borders between its places, but not the padding around it. Ranges that reach past the end of the grid
+    /// are cut off there.
+    ///
+    /// # Arguments
+    /// * `rows` - The rows of the grid to copy.
+    /// * `columns` - The columns of the grid to copy.
+    ///
+    /// # Returns
+    /// The pixels of the block, or an error if either range is empty or starts outside of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 15]);
+    ///
+    /// let block = merger.crop_tiles(1..3, 0..2).unwrap();
+    /// assert_eq!(block.dimensions(), (200, 200));
+    /// ```
+    pub fn crop_tiles(
+        &self,
+        rows: Range<u32>,
+        columns: Range<u32>,
+    ) -> image::ImageResult<ImageBuffer<P, Vec<P::Subpixel>>> {
+        let padding = self.padding.unwrap_or(Padding { x: 0, y: 0 });
+        let (cell_width, cell_height) = self.cell_dimensions();
+
+        // Returns the start and length in pixels of a range of cells along one axis.
+        let span = |range: Range<u32>, cell: u32, padding: u32| {
+            let stride = cell.saturating_add(padding);
+            let start = range.start.saturating_mul(stride);
+            let length = match range.len() as u32 {
+                0 => 0,
+                cells => (cells - 1).saturating_mul(stride).saturating_add(cell),
+            };
+            (start, length)
+        };
+        let (x, width) = span(columns, cell_width, padding.x);
+        let (y, height) = span(rows, cell_height, padding.y);
+
+        self.crop_region(x, y.saturating_add(self.band_heights().0), width, height)
+    }
+
+    /// Returns a copy of the canvas with the empty rows of pixels at the bottom cut off, for example the rows left behind by
+    /// removing images. A row is empty if every subpixel in it is zero, which means fully transparent for pixels with an alpha
+    /// channel. Rows are only cut off up to the first row that is not empty, and never past the bottom of an occupied place.
+    ///
+    /// If the merger was set to `with_tight_last_row`, the empty places at the end of a lone partial row are cropped as well,
+    /// whatever they are filled with. When they can not be cropped, for example below a full row, they are cleared to zero.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgba};
+    ///
+    /// let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new_from_pixel(100, 100, Rgba([255; 4])); 6]);
+    /// merger.remove_image(5);
+    ///
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 200));
+    /// assert_eq!(merger.trim_transparent().dimensions(), (500, 100));
+    /// ```
+    pub fn trim_transparent(&self) -> ImageBuffer<P, Vec<P::Subpixel>> {
+        let (width, height) = self.canvas.dimensions();
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let row_len = width as usize * channels;
+
+        // The lowest place that holds an image is in the last line in row major order, but in the first line otherwise.
+        let occupied_height = match self.num_images.checked_sub(1) {
+            Some(last_index) => {
+                let lowest_index = match (self.fill_order, self.fill_directions.1) {
+                    // The first image is in the bottom row when filling bottom to top.
+                    (_, VerticalDirection::BottomToTop) => 0,
+                    (FillOrder::RowMajor, _) => last_index,
+                    (FillOrder::ColumnMajor, _) => last_index.min(self.images_per_line - 1),
+                };
+                let (_, y) = self.get_paste_coordinates_unchecked(lowest_index);
+                (y + self.image_dimensions.1 + self.border_thickness() + self.caption_height)
+                    .min(height)
+            }
+            None => 0,
+        };
+
+        // The empty places can only be cropped when no other line reaches past them.
+        let lone_partial_line =
+            self.tight_last_row && self.num_images > 0 && self.num_images < self.images_per_line;
+        let mut trimmed_width = width;
+        let mut trimmed_height = height;
+        if lone_partial_line {
+            match self.fill_order {
+                // Filling right to left leaves the empty places at the start of the row, where they can not be cropped.
+                FillOrder::RowMajor
+                    if self.fill_directions.0 == HorizontalDirection::RightToLeft => {}
+                FillOrder::RowMajor => {
+                    let (x, _) = self.get_paste_coordinates_unchecked(self.num_images - 1);
+                    trimmed_width =
+                        (x + self.image_dimensions.0 + self.border_thickness()).min(width);
+                }
+                // Cropping the bottom of a lone column would cut off the footer below it.
+                FillOrder::ColumnMajor if self.footer.is_none() => trimmed_height = occupied_height,
+                FillOrder::ColumnMajor => {}
+            }
+        }
+
+        let buffer: &[P::Subpixel] = &self.canvas;
+        let trimmed_row_len = trimmed_width as usize * channels;
+        while trimmed_height > occupied_height {
+            let start = (trimmed_height - 1) as usize * row_len;
+            if buffer[start..start + trimmed_row_len]
+                .iter()
+                .any(|s| !s.is_zero())
+            {
+                break;
+            }
+            trimmed_height -= 1;
+        }
+
+        let mut container: Vec<P::Subpixel> = buffer
+            .chunks_exact(row_len)
+            .take(trimmed_height as usize)
+            .flat_map(|row| &row[..trimmed_row_len])
+            .copied()
+            .collect();
+
+        // Whatever was not cropped of the empty places is cleared, which is all of them once there is more than one line.
+        if let Some((x, y, place_width, place_height)) =
+            self.trailing_places_rect().filter(|_| self.tight_last_row)
+        {
+            let start = x.min(trimmed_width) as usize * channels;
+            let end = x.saturating_add(place_width).min(trimmed_width) as usize * channels;
+            container
+                .chunks_exact_mut(trimmed_row_len)
+                .take(y.saturating_add(place_height) as usize)
+                .skip(y as usize)
+                .for_each(|row| row[start..end].fill(Zero::zero()));
+        }
+
+        // Can always unwrap here because the buffer is always the right size.
+        ImageBuffer::from_raw(trimmed_width, trimmed_height, container).unwrap()
+    }
+
+    /// Encodes the images on the canvas as the frames of an animated GIF, in the order they were pushed, instead of as a grid.
+    /// This is useful when the merger is used to collect the frames of an animation. Padding, borders, empty places and holes
+    /// are not part of the frames, and the animation loops forever.
+    ///
+    /// # Arguments
+    /// * `writer` - The writer to write the GIF to.
+    /// * `frame_delay` - How long each frame is shown for. GIFs store delays in hundredths of a second, so the delay is rounded.
+    ///
+    /// # Returns
+    /// An error if the GIF could not be encoded or written.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgba};
+    /// use std::time::Duration;
+    ///
+    /// let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 3]);
+    ///
+    /// let mut gif = Vec::new();
+    /// merger.encode_as_gif(&mut gif, Duration::from_millis(100)).unwrap();
+    /// assert!(gif.starts_with(b"GIF89a"));
+    /// ```
+    pub fn encode_as_gif<W: Write>(
+        &self,
+        writer: W,
+        frame_delay: Duration,
+    ) -> image::ImageResult<()>
+    where
+        ImageBuffer<P, Vec<P::Subpixel>>: ConvertBuffer<RgbaImage>,
+    {
+        let mut encoder = GifEncoder::new(writer);
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let delay = Delay::from_saturating_duration(frame_delay);
+        encoder.encode_frames(
+            self.tiles()
+                .map(|(_, tile)| Frame::from_parts(tile.to_image().convert(), 0, 0, delay)),
+        )
+    }
+
+    fn check_index(&self, index: u32) -> Result<(), MergeError> {
+        if index >= self.num_images {
+            return Err(MergeError::IndexOutOfBounds {
+                index,
+                num_images: self.num_images,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether a canvas holding the given number of lines takes up no more memory than the memory budget, if there is
+    /// one.
+    fn within_budget(&self, lines: u32) -> bool {
+        self.memory_budget.is_none_or(|budget| {
+            self.checked_canvas_dimensions_for(lines)
+                .is_some_and(|(width, height)| Self::bytes_for(width, height) <= budget)
+        })
+    }
+
+    /// Returns the dimensions, (x, y), in pixels of a canvas holding the given number of lines, or `None` if the canvas would
+    /// be too large to address.
+    fn checked_canvas_dimensions_for(&self, lines: u32) -> Option<(u32, u32)> {
+        let (columns, rows) = self.grid_size(lines);
+        let (width, height) = Self::checked_grid_dimensions(
+            self.cell_dimensions(),
+            columns,
+            rows,
+            self.padding.unwrap_or(Padding { x: 0, y: 0 }),
+        )?;
+        let (header_height, footer_height) = self.band_heights();
+        let height = height
+            .checked_add(header_height)?
+            .checked_add(footer_height)?;
+
+        // A Vec can never hold more than isize::MAX bytes.
+        let bytes = (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(<P as Pixel>::CHANNEL_COUNT as usize)?
+            .checked_mul(std::mem::size_of::<P::Subpixel>())?;
+        (bytes <= isize::MAX as usize).then_some((width, height))
+    }
+
+    /// Checks that the canvas is allowed to grow so it holds the given number of lines.
+    fn check_can_grow(&self, lines: u32) -> Result<(), MergeError> {
+        if self.fixed_size {
+            return Err(MergeError::OutOfCapacity {
+                capacity: self.capacity(),
+            });
+        }
+
+        self.check_fits(lines)
+    }
+
+    /// Checks that a canvas holding the given number of lines is no larger than the maximum canvas dimensions, within the
+    /// memory budget, and small enough to fit in memory.
+    fn check_fits(&self, lines: u32) -> Result<(), MergeError> {
+        let fits = match self.checked_canvas_dimensions_for(lines) {
+            Some((width, height)) => {
+                self.max_canvas_dimensions
+                    .is_none_or(|(max_width, max_height)| {
+                        width <= max_width && height <= max_height
+                    })
+                    && self.within_budget(lines)
+            }
+            None => false,
+        };
+        if !fits {
+            return Err(MergeError::CanvasTooLarge {
+                max_dimensions: self.max_canvas_dimensions,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the squares of a checkerboard background are at least one pixel in size.
+    fn check_background(&self) -> Result<(), MergeError> {
+        if let Some(Background::Checker { size: 0, .. }) = self.background {
+            return Err(MergeError::InvalidConfiguration(
+                "the squares of a checkerboard background must be at least one pixel in size",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a merger with a memory budget fills in row major order from the top down, the only order in which whole
+    /// rows are completed at the top of the canvas, where they can be flushed off of it.
+    fn check_fill_order(&self) -> Result<(), MergeError> {
+        if self.memory_budget.is_none() {
+            return Ok(());
+        }
+        if self.fill_order == FillOrder::ColumnMajor {
+            return Err(MergeError::InvalidConfiguration(
+                "a merger with a memory budget can only be filled in row major order",
+            ));
+        }
+        if self.fill_directions.1 == VerticalDirection::BottomToTop {
+            return Err(MergeError::InvalidConfiguration(
+                "a merger with a memory budget can only be filled top to bottom",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the memory budget, if set, is large enough to hold a single row of images.
+    fn check_memory_budget(&self) -> Result<(), MergeError> {
+        if !self.within_budget(1) {
+            return Err(MergeError::InvalidConfiguration(
+                "the memory budget must be large enough to hold a single row of images",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the given image is the right size, or can be fitted to the right size by `fit_image`.
+    fn check_fittable(&self, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        if self.can_fit(image) {
+            return Ok(());
+        }
+
+        self.check_dimensions(image)
+    }
+
+    fn check_dimensions(&self, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        if image.dimensions() != self.image_dimensions {
+            return Err(MergeError::DimensionMismatch {
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Pushes an image onto the canvas, growing the canvas if there is no more space on it. Unlike `push`, this method
+    /// checks the dimensions of the image first.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. Its dimensions must match the image dimensions of the merger, unless the
+    ///   merger was set to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the same size as the
+    ///   image dimensions of the merger, a [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed
+    ///   grid and every cell is filled, a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would have
+    ///   to grow past its maximum dimensions, or a [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is
+    ///   not enough memory to grow it. Nothing is pasted in any of these cases.
+    pub fn try_push(&mut self, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        let fitted = self.fit_image(image, self.next_index());
+        let image = fitted.as_ref().unwrap_or(image);
+        self.check_dimensions(image)?;
+
+        let (x, y) = self.next_place()?;
+        paste(&self.canvas, image, Point { x, y });
+        log_event!(
+            trace,
+            "Pasted the image at index {} at ({}, {}).",
+            self.next_index(),
+            x,
+            y
+        );
+
+        self.num_images += 1;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Pushes an image onto the canvas like `try_push`, and returns the index it was placed at. The index can be passed straight
+    /// to methods like `replace_image`, `get_image_at` or `remove_image` to refer to the image later, as long as no image
+    /// before it is removed in the meantime.
+    ///
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. Its dimensions must match the image dimensions of the merger, unless the
+    ///   merger was set to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - The index the image was placed at.
+    /// * `Err` - Any of the errors of `try_push`. Nothing is pasted in this case.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 3]);
+    ///
+    /// let index = merger.push_indexed(&BufferedImage::new_from_pixel(100, 100, Rgb([255, 0, 0]))).unwrap();
+    /// assert_eq!(index, 3);
+    /// assert_eq!(merger.get_image_at(index).unwrap().get_pixel(0, 0), &Rgb([255, 0, 0]));
+    /// ```
+    pub fn push_indexed(&mut self, image: &BufferedImage<P>) -> Result<u32, MergeError> {
+        self.try_push(image)?;
+
+        // Can always unwrap here because an image was just pasted.
+        Ok(self.last_pasted_index.unwrap())
+    }
+
+    /// Pastes an image at the given index instead of the next free place, growing the canvas to include the row (or column in
+    /// column major order) of the index if needed. This allows for sparse layouts, the places skipped over are left empty and
+    /// hold the background. Pasting at an index that already holds an image replaces it.
+    ///
+    /// The places up to the highest index that has been pasted to count as occupied, and `push` continues from the place after
+    /// it.
+    ///
+    /// # Arguments
+    /// * `index` - The index to paste the image at.
+    /// * `image` - The image to paste. Its dimensions must match the image dimensions of the merger, unless the merger was set
+    ///   to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err` - Any of the errors of `try_push`. Nothing is pasted in this case.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.push_at(12, &BufferedImage::new(100, 100)).unwrap();
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 300));
+    ///
+    /// merger.push(&BufferedImage::new(100, 100));
+    /// assert_eq!(merger.last_pasted_index(), Some(13));
+    /// ```
+    pub fn push_at(&mut self, index: u32, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        let fitted = self.fit_image(image, index);
+        let image = fitted.as_ref().unwrap_or(image);
+        self.check_dimensions(image)?;
+
+        let (x, y) = self.place_of(index)?;
+        paste(&self.canvas, image, Point { x, y });
+        log_event!(
+            trace,
+            "Pasted the image at index {} at ({}, {}).",
+            index,
+            x,
+            y
+        );
+
+        self.mark_occupied(index);
+        Ok(())
+    }
+
+    /// Records that the place at the given index holds an image, turning any places skipped over before it into holes.
+    fn mark_occupied(&mut self, index: u32) {
+        self.holes.remove(&index);
+        self.holes.extend(self.num_images..index);
+        self.num_images = self.num_images.max(index + 1);
+        self.last_pasted_index = self.num_images.checked_sub(1);
+    }
+
+    /// Paints the place at the given index a solid color instead of pasting an image into it, growing the canvas to include
+    /// the place if needed. The place counts as occupied afterwards, just like with `push_at`, so this can be used for
+    /// placeholders in a grid. The border around the place, if any, is left as it is.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the place to paint.
+    /// * `color` - The color to paint the place with.
+    ///
+    /// # Returns
+    /// * `Ok` - If the place was painted.
+    /// * `Err` - A [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed grid and the index is not
+    ///   on it, a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would have to grow past its maximum
+    ///   dimensions, or a [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory to grow
+    ///   it. Nothing is painted in any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.push(&BufferedImage::new(100, 100));
+    /// merger.fill_cell(1, Rgb([128, 128, 128])).unwrap();
+    ///
+    /// assert_eq!(merger.get_num_images(), 2);
+    /// assert_eq!(*merger.get_canvas().get_pixel(150, 50), Rgb([128, 128, 128]));
+    /// ```
+    pub fn fill_cell(&mut self, index: u32, color: P) -> Result<(), MergeError> {
+        let (x, y) = self.place_of(index)?;
+        let (width, height) = self.image_dimensions;
+
+        let canvas: &mut ImageBuffer<P, Vec<P::Subpixel>> = self.canvas.get_image_mut();
+        for row in y..y + height {
+            for column in x..x + width {
+                canvas.put_pixel(column, row, color);
+            }
+        }
+
+        self.mark_occupied(index);
+        Ok(())
+    }
+
+    /// Pushes an image onto the canvas, blending its pixels with whatever is already on the canvas in its place instead of
+    /// copying them, growing the canvas if there is no more space on it. The place the image is pushed to only holds the
+    /// background color, unless the canvas was drawn on, so this is most useful together with `replace_image_blended`.
+    ///
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. Its dimensions must match the image dimensions of the merger, unless the
+    ///   merger was set to resize images on push with `with_resize_on_push`.
+    /// * `mode` - How the pixels of the image are combined with the canvas.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - Any of the errors of `try_push`.
+    pub fn push_blended(
+        &mut self,
+        image: &BufferedImage<P>,
+        mode: BlendMode,
+    ) -> Result<(), MergeError>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let fitted = self.fit_image(image, self.next_index());
+        let image = fitted.as_ref().unwrap_or(image);
+        self.check_dimensions(image)?;
+
+        self.next_place()?;
+        self.blend_image(self.next_index(), image, mode);
+        self.num_images += 1;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Blends an image on top of the image at the given index, combining their pixels with the given mode.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image to blend onto.
+    /// * `image` - The image to blend on top. Its dimensions must match the image dimensions of the merger, unless the merger
+    ///   was set to resize images on push with `with_resize_on_push`.
+    /// * `mode` - How the pixels of the image are combined with the canvas.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was blended onto the canvas.
+    /// * `Err` - A [MergeError::IndexOutOfBounds](MergeError::IndexOutOfBounds) if there is no image at the given index, or
+    ///   a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the right size.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, BlendMode, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.push(&BufferedImage::new_from_pixel(100, 100, Rgb([100, 0, 200])));
+    /// merger.replace_image_blended(0, &BufferedImage::new_from_pixel(100, 100, Rgb([100, 50, 100])), BlendMode::Add).unwrap();
+    ///
+    /// assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgb([200, 50, 255]));
+    /// ```
+    pub fn replace_image_blended(
+        &mut self,
+        index: u32,
+        image: &BufferedImage<P>,
+        mode: BlendMode,
+    ) -> Result<(), MergeError>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        self.check_index(index)?;
+        let fitted = self.fit_image(image, index);
+        let image = fitted.as_ref().unwrap_or(image);
+        self.check_dimensions(image)?;
+
+        self.blend_image(index, image, mode);
+        self.holes.remove(&index);
+        Ok(())
+    }
+
+    /// Draws a watermark over the whole canvas, blending it with the canvas at the given opacity. The watermark is drawn onto
+    /// the assembled canvas rather than onto each image, so it covers the padding and empty places as well. The images on the
+    /// canvas are not changed in any other way, and the number of images stays the same. Any part of the watermark that falls
+    /// outside of the canvas is cut off.
+    ///
+    /// # Arguments
+    /// * `watermark` - The image to draw over the canvas. Its alpha channel, if any, is respected.
+    /// * `position` - Where on the canvas to draw the watermark, or whether to tile it across the canvas.
+    /// * `opacity` - How opaque the watermark is drawn, from 0.0 (invisible) to 1.0.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, WatermarkPosition, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 5]);
+    ///
+    /// let watermark = BufferedImage::new_from_pixel(50, 20, Rgb([255, 255, 255]));
+    /// merger.apply_watermark(&watermark, WatermarkPosition::BottomRight, 0.5);
+    /// assert_eq!(merger.get_canvas().get_pixel(499, 99), &Rgb([128, 128, 128]));
+    /// assert_eq!(merger.get_num_images(), 5);
+    /// ```
+    pub fn apply_watermark<U>(
+        &mut self,
+        watermark: &Image<P, U>,
+        position: WatermarkPosition,
+        opacity: f32,
+    ) where
+        <P as Pixel>::Subpixel: Send,
+        U: image::GenericImage<Pixel = P> + Sync,
+    {
+        let (watermark_width, watermark_height) = watermark.dimensions();
+        if watermark_width == 0 || watermark_height == 0 {
+            return;
+        }
+
+        let (width, height) = self.canvas.dimensions();
+        let (free_x, free_y) = (
+            width as i64 - watermark_width as i64,
+            height as i64 - watermark_height as i64,
+        );
+        let (offset_x, offset_y) = match position {
+            WatermarkPosition::TopLeft | WatermarkPosition::Tiled => (0, 0),
+            WatermarkPosition::TopRight => (free_x, 0),
+            WatermarkPosition::BottomLeft => (0, free_y),
+            WatermarkPosition::BottomRight => (free_x, free_y),
+            WatermarkPosition::Center => (free_x / 2, free_y / 2),
+        };
+        let tiled = position == WatermarkPosition::Tiled;
+
+        // Returns the coordinate on the watermark for a coordinate on the canvas, if the watermark covers it.
+        let source = |canvas: u32, offset: i64, size: u32| -> Option<u32> {
+            if tiled {
+                return Some(canvas % size);
+            }
+            let coordinate = canvas as i64 - offset;
+            (0..size as i64)
+                .contains(&coordinate)
+                .then_some(coordinate as u32)
+        };
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+        buffer
+            .par_chunks_exact_mut(width as usize * channels)
+            .enumerate()
+            .for_each(|(y, row)| {
+                let Some(source_y) = source(y as u32, offset_y, watermark_height) else {
+                    return;
+                };
+                for (x, pixel) in row.chunks_exact_mut(channels).enumerate() {
+                    if let Some(source_x) = source(x as u32, offset_x, watermark_width) {
+                        let top = watermark.get_pixel(source_x, source_y);
+                        blend_pixel_with_opacity(P::from_slice_mut(pixel), &top, opacity);
+                    }
+                }
+            });
+    }
+
+    /// Blends the given image onto the place of the given index, row by row in parallel.
+    fn blend_image(&mut self, index: u32, image: &BufferedImage<P>, mode: BlendMode)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let canvas_row_len = self.canvas.width() as usize * channels;
+        let row_len = self.image_dimensions.0 as usize * channels;
+        let start = x as usize * channels;
+
+        let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+        let top: &[P::Subpixel] = image;
+        buffer
+            .par_chunks_exact_mut(canvas_row_len)
+            .skip(y as usize)
+            .zip(top.par_chunks_exact(row_len))
+            .for_each(|(canvas_row, image_row)| {
+                let canvas_row = &mut canvas_row[start..start + row_len];
+                for (bottom, top) in canvas_row
+                    .chunks_exact_mut(channels)
+                    .zip(image_row.chunks_exact(channels))
+                {
+                    blend_pixel(mode, P::from_slice_mut(bottom), P::from_slice(top));
+                }
+            });
+    }
+
+    /// Rotates or flips a copy of the image and pushes it onto the canvas, growing the canvas if there is no more space on it.
+    /// The image itself is left untouched.
+    ///
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. Its dimensions after the transform must match the image dimensions of
+    ///   the merger, so a rotation by 90 or 270 degrees needs an image that is as wide as the merger's images are tall.
+    /// * `transform` - The rotation or flip to apply.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - Any of the errors of `try_push`.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb, Transform};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 50), 5);
+    /// merger.push_with_transform(&BufferedImage::new(50, 100), Transform::Rotate90).unwrap();
+    /// assert!(merger.push_with_transform(&BufferedImage::new(50, 100), Transform::FlipVertical).is_err());
+    /// ```
+    pub fn push_with_transform(
+        &mut self,
+        image: &BufferedImage<P>,
+        transform: Transform,
+    ) -> Result<(), MergeError> {
+        let transformed = match transform {
+            Transform::Rotate90 => imageops::rotate90(&**image),
+            Transform::Rotate180 => imageops::rotate180(&**image),
+            Transform::Rotate270 => imageops::rotate270(&**image),
+            Transform::FlipHorizontal => imageops::flip_horizontal(&**image),
+            Transform::FlipVertical => imageops::flip_vertical(&**image),
+        };
+
+        self.try_push(&Image::from(transformed))
+    }
+
+    /// Pushes a region of a larger image onto the canvas, such as a single frame of a sprite atlas, growing the canvas if there
+    /// is no more space on it. The region is copied out of the image, so the image itself does not have to be cropped first.
+    ///
+    /// # Arguments
+    /// * `image` - The image to take the region from. It can be any image type with the pixel type of the canvas.
+    /// * `x` - The x coordinate of the top left corner of the region.
+    /// * `y` - The y coordinate of the top left corner of the region.
+    /// * `width` - The width of the region.
+    /// * `height` - The height of the region.
+    ///
+    /// # Returns
+    /// * `Ok` - If the region was pushed onto the canvas.
+    /// * `Err` - A [MergeError::RegionOutOfBounds](MergeError::RegionOutOfBounds) if the region does not lie completely within
+    ///   the image, or any of the errors of `try_push`, such as a
+    ///   [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the region is not the size of the merger's images.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let atlas: BufferedImage<Rgb<u8>> = BufferedImage::new(400, 100);
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// for frame in 0..4 {
+    ///     merger.push_region(&atlas, frame * 100, 0, 100, 100).unwrap();
+    /// }
+    /// assert!(merger.push_region(&atlas, 350, 0, 100, 100).is_err());
+    /// ```
+    pub fn push_region<U>(
+        &mut self,
+        image: &Image<P, U>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), MergeError>
+    where
+        U: image::GenericImage<Pixel = P>,
+    {
+        let (image_width, image_height) = image.dimensions();
+        if x as u64 + width as u64 > image_width as u64
+            || y as u64 + height as u64 > image_height as u64
+        {
+            return Err(MergeError::RegionOutOfBounds {
+                region: (x, y, width, height),
+                dimensions: (image_width, image_height),
+            });
+        }
+
+        let region = ImageBuffer::from_fn(width, height, |offset_x, offset_y| {
+            image.get_pixel(x + offset_x, y + offset_y)
+        });
+        self.try_push(&Image::from(region))
+    }
+
+    /// Pushes an image given as raw subpixels onto the canvas, such as pixels read back from a GPU, growing the canvas if there
+    /// is no more space on it. The subpixels are copied straight onto the canvas row by row, so they never have to be wrapped
+    /// in an [Image](Image) first. Raw images are never resized, even if the merger was set to resize images on push.
+    ///
+    /// # Arguments
+    /// * `data` - The subpixels of the image, row by row, with `CHANNEL_COUNT` subpixels per pixel.
+    /// * `width` - The width of the image.
+    /// * `height` - The height of the image.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::BufferSizeMismatch](MergeError::BufferSizeMismatch) if `data` does not hold exactly as many
+    ///   subpixels as an image of the given dimensions, a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the
+    ///   dimensions do not match the image dimensions of the merger, or any of the errors of `try_push`. Nothing is pasted in
+    ///   any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((2, 2), 5);
+    /// merger.push_raw(&[255; 12], 2, 2).unwrap();
+    /// assert_eq!(merger.get_canvas().get_pixel(1, 1), &Rgb([255, 255, 255]));
+    ///
+    /// assert!(merger.push_raw(&[255; 11], 2, 2).is_err());
+    /// ```
+    pub fn push_raw(
+        &mut self,
+        data: &[P::Subpixel],
+        width: u32,
+        height: u32,
+    ) -> Result<(), MergeError> {
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(channels))
+            .unwrap_or(usize::MAX);
+        if data.len() != expected {
+            return Err(MergeError::BufferSizeMismatch {
+                expected,
+                got: data.len(),
+            });
+        }
+        if (width, height) != self.image_dimensions {
+            return Err(MergeError::DimensionMismatch {
+                expected: self.image_dimensions,
+                got: (width, height),
+            });
+        }
+
+        let (x, y) = self.next_place()?;
+        let canvas_width = self.canvas.width() as usize;
+        let row_len = width as usize * channels;
+
+        let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+        for (row, source) in data.chunks_exact(row_len).enumerate() {
+            let start = ((y as usize + row) * canvas_width + x as usize) * channels;
+            buffer[start..start + row_len].copy_from_slice(source);
+        }
+        log_event!(
+            trace,
+            "Pasted the image at index {} at ({}, {}).",
+            self.next_index(),
+            x,
+            y
+        );
+
+        self.num_images += 1;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Converts a copy of an image with a different pixel type to the pixel type of the canvas, and pushes it onto the canvas,
+    /// growing the canvas if there is no more space on it. This uses the color conversions of the image crate, so for example
+    /// `Rgb` images can be pushed onto an `Rgba` canvas and become fully opaque. The image itself is left untouched.
+    ///
+    /// # Arguments
+    /// * `image` - The image to convert and push onto the canvas. Its dimensions must match the image dimensions of the
+    ///   merger, unless the merger was set to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - Any of the errors of `try_push`.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb, Rgba};
+    ///
+    /// let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 5);
+    /// let image: BufferedImage<Rgb<u8>> = BufferedImage::new_from_pixel(100, 100, Rgb([255, 0, 0]));
+    /// merger.push_converting(&image).unwrap();
+    ///
+    /// assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+    /// ```
+    pub fn push_converting<Q, Container>(
+        &mut self,
+        image: &Image<Q, ImageBuffer<Q, Container>>,
+    ) -> Result<(), MergeError>
+    where
+        Q: Pixel,
+        Container: DerefMut<Target = [Q::Subpixel]>,
+        ImageBuffer<Q, Container>: ConvertBuffer<ImageBuffer<P, Vec<P::Subpixel>>>,
+    {
+        self.try_push(&Image::from(image.convert()))
+    }
+
+    /// Opens the image file at the given path and pushes it onto the canvas, growing the canvas if there is no more space on it.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the image file to push. The image's dimensions must match the image dimensions of the merger.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::Image](MergeError::Image) if the file could not be read or decoded, or a
+    ///   [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the right size.
+    pub fn push_path<Q: AsRef<Path>>(&mut self, path: Q) -> Result<(), MergeError>
+    where
+        BufferedImage<P>: FromPath,
+    {
+        let image = BufferedImage::<P>::from_path(path)?;
+        self.try_push(&image)
+    }
+
+    /// Decodes the given bytes of an image file and pushes the image onto the canvas, growing the canvas if there is no more
+    /// space on it. The image format is derived from the contents of the bytes.
+    ///
+    /// # Arguments
+    /// * `bytes` - The encoded image. The image's dimensions must match the image dimensions of the merger.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::Image](MergeError::Image) if the bytes could not be decoded, or any of the errors of
+    ///   `try_push`.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), MergeError>
+    where
+        BufferedImage<P>: FromBytes,
+    {
+        let image = BufferedImage::<P>::from_bytes(bytes)?;
+        self.try_push(&image)
+    }
+
+    /// Downloads the image at the given URL with the given fetcher, and pushes it onto the canvas once it has been downloaded.
+    /// The fetcher is awaited, so the download does not block the runtime, and any HTTP client can be used with it.
+    ///
+    /// The image is decoded and pasted synchronously on the task awaiting this future once the download finishes, which
+    /// blocks the executor thread for as long as decoding takes. Use `push_url_with_spawner` to decode on a blocking thread
+    /// pool, such as tokio's `spawn_blocking`, instead.
+    ///
+    /// This method is only available with the `async` feature.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the image, which is passed on to the fetcher.
+    /// * `fetch` - Downloads the bytes of the image at the given URL.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::Image](MergeError::Image) if the image could not be downloaded or decoded, or any of the
+    ///   errors of `try_push`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example() -> Result<(), image_merger::MergeError> {
+    /// use image_merger::{GrowableMerger, Rgba};
+    ///
+    /// let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger
+    ///     .push_url("https://example.com/image.png", |url| {
+    ///         let url = url.to_owned();
+    ///         async move {
+    ///             // Download the image with any HTTP client here.
+    ///             std::fs::read(url.trim_start_matches("https://example.com/"))
+    ///         }
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn push_url<F, Fut>(&mut self, url: &str, fetch: F) -> Result<(), MergeError>
+    where
+        F: FnOnce(&str) -> Fut,
+        Fut: std::future::Future<Output = std::io::Result<Vec<u8>>>,
+        BufferedImage<P>: FromBytes,
+    {
+        let bytes = fetch(url).await.map_err(image::ImageError::IoError)?;
+        self.push_bytes(&bytes)
+    }
+
+    /// Downloads the image at the given URL with the given fetcher like `push_url` does, but hands decoding off to the given
+    /// spawner so it does not block the executor. Only pasting the decoded image, which copies its pixels, runs on the task
+    /// awaiting this future.
+    ///
+    /// This method is only available with the `async` feature.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the image, which is passed on to the fetcher.
+    /// * `fetch` - Downloads the bytes of the image at the given URL.
+    /// * `spawn_blocking` - Runs the decoding closure it is given on a thread where blocking is allowed, and resolves to its
+    ///   result.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::Image](MergeError::Image) if the image could not be downloaded or decoded, or any of the
+    ///   errors of `try_push`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # async fn example() -> Result<(), image_merger::MergeError> {
+    /// use image_merger::{GrowableMerger, Rgba};
+    ///
+    /// let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger
+    ///     .push_url_with_spawner(
+    ///         "https://example.com/image.png",
+    ///         |url| {
+    ///             let url = url.to_owned();
+    ///             async move { std::fs::read(url.trim_start_matches("https://example.com/")) }
+    ///         },
+    ///         // With tokio this would be `tokio::task::spawn_blocking(decode).await.unwrap()`.
+    ///         |decode| async move { std::thread::spawn(decode).join().unwrap() },
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn push_url_with_spawner<F, Fut, S, SFut>(
+        &mut self,
+        url: &str,
+        fetch: F,
+        spawn_blocking: S,
+    ) -> Result<(), MergeError>
+    where
+        P: Send,
+        F: FnOnce(&str) -> Fut,
+        Fut: std::future::Future<Output = std::io::Result<Vec<u8>>>,
+        S: FnOnce(Box<dyn FnOnce() -> image::ImageResult<BufferedImage<P>> + Send>) -> SFut,
+        SFut: std::future::Future<Output = image::ImageResult<BufferedImage<P>>>,
+        BufferedImage<P>: FromBytes,
+    {
+        let bytes = fetch(url).await.map_err(image::ImageError::IoError)?;
+        let image =
+            spawn_blocking(Box::new(move || BufferedImage::<P>::from_bytes(&bytes))).await?;
+        self.try_push(&image)
+    }
+
+    /// Pushes every image of an iterator onto the canvas in order, one at a time, so only the image being pushed has to be held
+    /// in memory. This works with lazy iterators, such as one that opens image files as it goes. If the iterator knows how many
+    /// images it holds at least, the canvas grows once to fit them before any are pushed.
+    ///
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas. Their dimensions must match the image dimensions of the merger,
+    ///   unless the merger was set to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - The number of images that were pushed onto the canvas.
+    /// * `Err` - The first error of `try_push` that was hit. The images before it stay on the canvas, and the rest of the
+    ///   iterator is not consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// let images = (0..12).map(|_| BufferedImage::new(100, 100));
+    /// assert_eq!(merger.push_iter(images).unwrap(), 12);
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 300));
+    /// ```
+    pub fn push_iter<I>(&mut self, images: I) -> Result<u32, MergeError>
+    where
+        I: IntoIterator<Item = BufferedImage<P>>,
+    {
+        let images = images.into_iter();
+
+        // A canvas that can not grow to fit every image is left to grow as the images are pushed, without failing early.
+        let (min_images, _) = images.size_hint();
+        let required_lines = u32::try_from(min_images)
+            .ok()
+            .and_then(|len| self.num_images.checked_add(len))
+            .map(|required| required.div_ceil(self.images_per_line));
+        if let Some(required_lines) = required_lines {
+            if required_lines > self.total_lines && self.check_can_grow(required_lines).is_ok() {
+                // If the canvas can not be allocated in one go, it is left to grow as the images are pushed one by one.
+                let _ = self.grow_canvas_by(required_lines - self.total_lines);
+            }
+        }
+
+        let mut pushed = 0;
+        for image in images {
+            self.try_push(&image)?;
+            pushed += 1;
+        }
+        Ok(pushed)
+    }
+
+    /// Pushes every image file in the given directory onto the canvas, in order of their file names. Files that do not have an
+    /// image file extension are skipped, as are any subdirectories.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory to push the images of.
+    /// * `skip_errors` - If true, image files that can not be decoded or are not the right size are skipped instead of
+    ///   stopping with an error.
+    ///
+    /// # Returns
+    /// * `Ok` - The number of images that were pushed onto the canvas.
+    /// * `Err` - A [MergeError::Image](MergeError::Image) if the directory could not be read, or the first error that was hit
+    ///   while pushing an image when `skip_errors` is false. Any images before it will have already been pushed.
+    pub fn push_dir<Q: AsRef<Path>>(&mut self, dir: Q, skip_errors: bool) -> Result<u32, MergeError>
+    where
+        BufferedImage<P>: FromPath,
+    {
+        let mut paths = std::fs::read_dir(dir)
+            .map_err(image::ImageError::from)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(image::ImageError::from)?;
+        paths.retain(|path| path.is_file() && image::ImageFormat::from_path(path).is_ok());
+        paths.sort();
+
+        let total = paths.len() as u32;
+        let mut pushed = 0;
+        for (done, path) in paths.into_iter().enumerate() {
+            match self.push_path(path) {
+                Ok(()) => pushed += 1,
+                Err(_) if skip_errors => {}
+                Err(err) => return Err(err),
+            }
+
+            if let Some(callback) = self.progress_callback.as_mut() {
+                callback(done as u32 + 1, total);
+            }
+        }
+
+        Ok(pushed)
+    }
+
+    /// Saves every image on the canvas to its own file in the given directory, the inverse of `push_dir`. This is useful to
+    /// slice a sprite sheet back into its frames. Only the places that hold an image are saved, so holes and the empty places
+    /// at the end of the canvas are skipped. The image format of each file is derived from the file extension of its name.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory to save the images in. It must already exist.
+    /// * `name_fn` - Returns the file name to save the image at the given index as.
+    ///
+    /// # Returns
+    /// An error if any of the images could not be encoded or written. Any images before it will have already been saved.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 3]);
+    /// merger.export_tiles("frames", |index| format!("frame_{:03}.png", index)).unwrap();
+    /// ```
+    pub fn export_tiles<Q, F>(&self, dir: Q, name_fn: F) -> image::ImageResult<()>
+    where
+        Q: AsRef<Path>,
+        F: Fn(u32) -> String,
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        let dir = dir.as_ref();
+        for (index, tile) in self.tiles() {
+            tile.to_image().save(dir.join(name_fn(index)))?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the image at the given index with a new image, without moving any of the other images on the canvas. Every
+    /// pixel of the old image is overwritten, so a transparent replacement does not leave any of the old image behind.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image to replace.
+    /// * `image` - The image to paste in its place. Its dimensions must match the image dimensions of the merger.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was replaced.
+    /// * `Err` - A [MergeError::IndexOutOfBounds](MergeError::IndexOutOfBounds) if there is no image at the given index, or
+    ///   a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the right size.
+    pub fn replace_image(
+        &mut self,
+        index: u32,
+        image: &BufferedImage<P>,
+    ) -> Result<(), MergeError> {
+        self.check_index(index)?;
+        self.check_dimensions(image)?;
+
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        paste(&self.canvas, image, Point { x, y });
+        self.holes.remove(&index);
+        Ok(())
+    }
+
+    /// Appends every image of another merger onto the end of this canvas in the order they were pushed, growing the canvas
+    /// once to fit all of them. This is equivalent to pushing each of the other merger's images, but the pixels are copied
+    /// straight from one canvas to the other.
+    ///
+    /// # Arguments
+    /// * `other` - The merger to take the images from. It must hold images of the same size, and have the same number of
+    ///   images per row.
+    ///
+    /// # Returns
+    /// * `Ok` - If every image was appended.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the other merger holds images of a
+    ///   different size, a [MergeError::ImagesPerRowMismatch](MergeError::ImagesPerRowMismatch) if it has a different number
+    ///   of images per row, or any of the errors of `try_bulk_push` if the canvas can not grow to fit them. Nothing is
+    ///   pasted in any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let image = BufferedImage::new(100, 100);
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&image; 3]);
+    /// let mut other: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// other.bulk_push(&[&image; 4]);
+    ///
+    /// merger.append(other).unwrap();
+    /// assert_eq!(merger.get_num_images(), 7);
+    /// ```
+    pub fn append(&mut self, other: GrowableMerger<P>) -> Result<(), MergeError> {
+        if other.image_dimensions != self.image_dimensions {
+            return Err(MergeError::DimensionMismatch {
+                expected: self.image_dimensions,
+                got: other.image_dimensions,
+            });
+        }
+        if other.images_per_line != self.images_per_line {
+            return Err(MergeError::ImagesPerRowMismatch {
+                expected: self.images_per_line,
+                got: other.images_per_line,
+            });
+        }
+
+        let required_lines = self
+            .num_images
+            .checked_add(other.num_images)
+            .ok_or(MergeError::CanvasTooLarge {
+                max_dimensions: self.max_canvas_dimensions,
+            })?
+            .div_ceil(self.images_per_line);
+        if required_lines > self.total_lines {
+            self.check_can_grow(required_lines)?;
+            self.grow_canvas_by(required_lines - self.total_lines)?;
+        }
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let row_len = self.image_dimensions.0 as usize * channels;
+        let other_width = other.canvas.width() as usize;
+        let canvas_width = self.canvas.width() as usize;
+
+        let source: &[P::Subpixel] = &other.canvas;
+        for index in 0..other.num_images {
+            let (from_x, from_y) = other.get_paste_coordinates_unchecked(index);
+            let (to_x, to_y) = self.get_paste_coordinates_unchecked(self.num_images + index);
+
+            let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+            for row in 0..self.image_dimensions.1 as usize {
+                let src = ((from_y as usize + row) * other_width + from_x as usize) * channels;
+                let dst = ((to_y as usize + row) * canvas_width + to_x as usize) * channels;
+                buffer[dst..dst + row_len].copy_from_slice(&source[src..src + row_len]);
+            }
+        }
+
+        self.holes
+            .extend(other.holes.iter().map(|hole| self.num_images + hole));
+        self.num_images += other.num_images;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Lays the canvas out again with a different number of images per row (or per column in column major order), moving
+    /// every image to its place in the new grid. The images keep their order, and the canvas keeps at least as many places as
+    /// it had before. The canvas is re-allocated at its new size and the pixels of every image are copied over row by row, as
+    /// the length of a row of pixels changes with the number of images in it.
+    ///
+    /// # Arguments
+    /// * `new_images_per_row` - The new number of images per row.
+    ///
+    /// # Returns
+    /// * `Ok` - If the canvas was laid out again.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `new_images_per_row` is zero, or a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the new canvas would be larger than the maximum canvas
+    ///   dimensions, or a [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory for it.
+    ///   Nothing is moved in any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 10]);
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 200));
+    ///
+    /// merger.reflow(2).unwrap();
+    /// assert_eq!(merger.get_canvas().dimensions(), (200, 500));
+    /// ```
+    pub fn reflow(&mut self, new_images_per_row: u32) -> Result<(), MergeError> {
+        check_layout(self.image_dimensions, new_images_per_row)?;
+        if new_images_per_row == self.images_per_line {
+            return Ok(());
+        }
+
+        let too_large = MergeError::CanvasTooLarge {
+            max_dimensions: self.max_canvas_dimensions,
+        };
+        let places = self.images_per_line as u64 * self.total_lines as u64;
+        let total_lines = u32::try_from(places.div_ceil(new_images_per_row as u64))
+            .map_err(|_| too_large)?
+            .max(1);
+
+        // The images are found on the old grid before the layout changes.
+        let old_places: Vec<(u32, u32)> = (0..self.num_images)
+            .map(|index| self.get_paste_coordinates_unchecked(index))
+            .collect();
+        let (old_images_per_line, old_total_lines) = (self.images_per_line, self.total_lines);
+
+        self.images_per_line = new_images_per_row;
+        self.total_lines = total_lines;
+        if let Err(err) = self.check_fits(total_lines) {
+            self.images_per_line = old_images_per_line;
+            self.total_lines = old_total_lines;
+            return Err(err);
+        }
+
+        if let Err(err) = self.reallocate_with_images(old_places) {
+            self.images_per_line = old_images_per_line;
+            self.total_lines = old_total_lines;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Allocates a new canvas for the current layout, and copies every image over from the given places on the old canvas to
+    /// the places their indices have on the new one. If there is not enough memory for the new canvas, a
+    /// [MergeError::AllocationFailed](MergeError::AllocationFailed) error is returned and the old canvas is kept.
+    fn reallocate_with_images(&mut self, old_places: Vec<(u32, u32)>) -> Result<(), MergeError> {
+        let old_canvas = std::mem::replace(&mut self.canvas, ImageCell::new(Image::new(0, 0)));
+        if let Err(err) = self.try_allocate_canvas() {
+            self.canvas = old_canvas;
+            return Err(err);
+        }
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let row_len = self.image_dimensions.0 as usize * channels;
+        let old_width = old_canvas.width() as usize;
+        let canvas_width = self.canvas.width() as usize;
+
+        let source: &[P::Subpixel] = &old_canvas;
+        for (index, (from_x, from_y)) in old_places.into_iter().enumerate() {
+            let (to_x, to_y) = self.get_paste_coordinates_unchecked(index as u32);
+
+            let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+            for row in 0..self.image_dimensions.1 as usize {
+                let src = ((from_y as usize + row) * old_width + from_x as usize) * channels;
+                let dst = ((to_y as usize + row) * canvas_width + to_x as usize) * channels;
+                buffer[dst..dst + row_len].copy_from_slice(&source[src..src + row_len]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Resizes the canvas to the given number of lines when the images fill towards the edge it grows at. Every place moves
+    /// when the number of lines changes, so the images are copied over to a new canvas instead of resizing it in place.
+    fn relocate_canvas(&mut self, total_lines: u32) -> Result<(), MergeError> {
+        log_event!(
+            debug,
+            "Resizing the canvas from {} to {} lines, moving every image.",
+            self.total_lines,
+            total_lines
+        );
+        let old_places: Vec<(u32, u32)> = (0..self.num_images)
+            .map(|index| self.get_paste_coordinates_unchecked(index))
+            .collect();
+
+        let old_lines = std::mem::replace(&mut self.total_lines, total_lines);
+        self.reallocate_with_images(old_places)
+            .inspect_err(|_| self.total_lines = old_lines)
+    }
+
+    /// Changes the number of images per row (or per column in column major order), laying every image already on the canvas
+    /// out again on the new grid with `reflow`. The images keep their order and their pixels, and the canvas is resized to
+    /// fit the new grid.
+    ///
+    /// # Arguments
+    /// * `images_per_row` - The new number of images per row.
+    ///
+    /// # Returns
+    /// * `Ok` - If the number of images per row was changed.
+    /// * `Err` - Any of the errors of `reflow`, such as a
+    ///   [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` is zero. The merger is left
+    ///   as it is in this case.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 10]);
+    ///
+    /// merger.set_images_per_row(10).unwrap();
+    /// assert_eq!(merger.get_num_columns(), 10);
+    /// assert_eq!(merger.get_canvas().dimensions(), (1000, 100));
+    /// ```
+    pub fn set_images_per_row(&mut self, images_per_row: u32) -> Result<(), MergeError> {
+        self.reflow(images_per_row)
+    }
+
+    /// Swaps the images at the two given indexes, without moving any of the other images on the canvas.
+    ///
+    /// # Arguments
+    /// * `a` - The index of the first image.
+    /// * `b` - The index of the second image.
+    ///
+    /// # Returns
+    /// * `Ok` - If the images were swapped.
+    /// * `Err` - A [MergeError::IndexOutOfBounds](MergeError::IndexOutOfBounds) if there is no image at either of the indexes.
+    ///   Nothing is moved in this case.
+    pub fn swap_images(&mut self, a: u32, b: u32) -> Result<(), MergeError> {
+        self.check_index(a)?;
+        self.check_index(b)?;
+        if a == b {
+            return Ok(());
+        }
+
+        // Can always unwrap here because the index was checked above.
+        let image = self.copy_cell(a);
+        self.copy_image(b, a);
+
+        let (x, y) = self.get_paste_coordinates_unchecked(b);
+        paste(&self.canvas, &image, Point { x, y });
+
+        // A hole moves along with the empty place it stands for.
+        if self.holes.contains(&a) != self.holes.contains(&b) {
+            if !self.holes.remove(&a) {
+                self.holes.remove(&b);
+                self.holes.insert(a);
+            } else {
+                self.holes.insert(b);
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts an image onto the canvas at the given index, shifting the image at that index and every image after it one
+    /// place towards the back of the canvas. The canvas grows if the shift needs a new row.
+    ///
+    /// # Arguments
+    /// * `index` - The index to insert the image at. Passing the number of images on the canvas pushes the image onto the end.
+    /// * `image` - The image to insert. Its dimensions must match the image dimensions of the merger, unless the merger was set
+    ///   to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was inserted.
+    /// * `Err` - A [MergeError::IndexOutOfBounds](MergeError::IndexOutOfBounds) if the index is past the end of the canvas,
+    ///   a [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the right size, a
+    ///   [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed grid and every cell is filled, a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would have to grow past its maximum
+    ///   dimensions, or a [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory to grow
+    ///   it. Nothing is moved in any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// let red = BufferedImage::new_from_pixel(100, 100, Rgb([255, 0, 0]));
+    /// let blue = BufferedImage::new_from_pixel(100, 100, Rgb([0, 0, 255]));
+    /// merger.push(&red);
+    /// merger.insert_image(0, &blue).unwrap();
+    ///
+    /// assert_eq!(merger.get_image_at(0), Some(blue));
+    /// assert_eq!(merger.get_image_at(1), Some(red));
+    /// ```
+    pub fn insert_image(&mut self, index: u32, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        if index > self.num_images {
+            return Err(MergeError::IndexOutOfBounds {
+                index,
+                num_images: self.num_images,
+            });
+        }
+
+        let fitted = self.fit_image(image, index);
+        let image = fitted.as_ref().unwrap_or(image);
+        self.check_dimensions(image)?;
+
+        if self.is_full() {
+            self.check_can_grow(self.total_lines + 1)?;
+            self.grow_canvas()?;
+        }
+
+        // Images only ever move towards the back, so going back to front never overwrites an image before it has been moved.
+        for from in (index..self.num_images).rev() {
+            self.copy_image(from, from + 1);
+        }
+
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        paste(&self.canvas, image, Point { x, y });
+
+        self.holes = self
+            .holes
+            .iter()
+            .map(|&hole| if hole >= index { hole + 1 } else { hole })
+            .collect();
+        self.num_images += 1;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Removes the most recently pushed image from the canvas, clearing its place to the background color. Unlike
+    /// `remove_image`, no other images need to be moved.
+    ///
+    /// # Returns
+    /// True if an image was removed, false if the canvas holds no images.
+    pub fn pop(&mut self) -> bool {
+        if self.num_images == 0 {
+            return false;
+        }
+
+        self.clear_image(self.num_images - 1);
+
+        self.num_images -= 1;
+        self.holes.remove(&self.num_images);
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        true
+    }
+
+    /// Removes every image from the canvas so the merger can be reused. The canvas is filled with the background color, but
+    /// its buffer is kept, so no allocation is needed to push the next batch of images.
+    pub fn clear(&mut self) {
+        let (width, height) = self.canvas.dimensions();
+        self.fill_background(0, 0, width, height);
+        self.draw_borders(0);
+        self.draw_bands();
+
+        self.last_pasted_index = None;
+        self.num_images = 0;
+        self.holes.clear();
+    }
+
+    /// Removes every image from the canvas like `clear`, and then resizes the canvas so it can hold the expected number of
+    /// images, as if the merger was constructed with `with_capacity`. A fixed grid keeps its size.
+    ///
+    /// # Arguments
+    /// * `expected_images` - The number of images the canvas should be able to hold before it needs to grow.
+    ///
+    /// # Returns
+    /// * `Ok` - If the canvas can hold the expected number of images.
+    /// * `Err` - A [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed grid that is too small, a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would grow past its maximum dimensions, or a
+    ///   [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory to resize it. The images
+    ///   are still removed in any of these cases, but the canvas keeps its size.
+    pub fn reset_to(&mut self, expected_images: u32) -> Result<(), MergeError> {
+        self.clear();
+        if self.fixed_size {
+            if expected_images > self.capacity() {
+                return Err(MergeError::OutOfCapacity {
+                    capacity: self.capacity(),
+                });
+            }
+            return Ok(());
+        }
+
+        let lines = expected_images.div_ceil(self.images_per_line).max(1);
+        if lines > self.total_lines {
+            self.check_can_grow(lines)?;
+        }
+        self.resize_canvas(lines)
+    }
+
+    /// Grows the canvas in a single allocation so it can hold at least `additional` more rows (or columns in column major
+    /// order) of images after the ones that already hold an image, so the pushes that follow do not have to grow it. This is
+    /// like constructing the merger `with_capacity`, but for a merger that is already in use. Nothing happens if the canvas is
+    /// already large enough.
+    ///
+    /// # Arguments
+    /// * `additional` - The number of rows to make room for, after the last row that holds an image.
+    ///
+    /// # Returns
+    /// * `Ok` - If the canvas can hold the rows.
+    /// * `Err` - A [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed grid that is too small, a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would grow past its maximum dimensions, or a
+    ///   [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory to grow it. The canvas is
+    ///   left as it is in any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 7]);
+    ///
+    /// merger.reserve_rows(3).unwrap();
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 500));
+    /// assert_eq!(merger.remaining_capacity(), 18);
+    /// ```
+    pub fn reserve_rows(&mut self, additional: u32) -> Result<(), MergeError> {
+        let required_lines = self
+            .num_images
+            .div_ceil(self.images_per_line)
+            .checked_add(additional)
+            .ok_or(MergeError::CanvasTooLarge {
+                max_dimensions: self.max_canvas_dimensions,
+            })?;
+        if required_lines <= self.total_lines {
+            return Ok(());
+        }
+
+        self.check_can_grow(required_lines)?;
+        self.grow_canvas_by(required_lines - self.total_lines)
+    }
+
+    /// Grows the canvas by the given number of rows (or columns in column major order) in a single allocation. The memory for
+    /// the canvas is reserved with a fallible allocation, so running out of memory returns an error instead of aborting the
+    /// process, which lets a long-running service turn away work it has no room for. Every push that grows the canvas
+    /// allocates the same way, this just makes the room up front.
+    ///
+    /// # Arguments
+    /// * `lines` - The number of rows to add to the canvas.
+    ///
+    /// # Returns
+    /// * `Ok` - If the canvas grew.
+    /// * `Err` - A [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed grid, a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would grow past its maximum dimensions, or a
+    ///   [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory to grow it. The canvas is
+    ///   left as it is in any of these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.try_grow(2).unwrap();
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 300));
+    /// assert_eq!(merger.remaining_capacity(), 15);
+    /// ```
+    pub fn try_grow(&mut self, lines: u32) -> Result<(), MergeError> {
+        if lines == 0 {
+            return Ok(());
+        }
+
+        let total_lines =
+            self.total_lines
+                .checked_add(lines)
+                .ok_or(MergeError::CanvasTooLarge {
+                    max_dimensions: self.max_canvas_dimensions,
+                })?;
+        self.check_can_grow(total_lines)?;
+        self.grow_canvas_by(lines)
+    }
+
+    /// Shrinks the canvas so it holds only the rows (or columns in column major order) that contain at least one image, and
+    /// frees any memory that is no longer needed. The canvas always keeps at least one row. This does nothing if the canvas
+    /// is already tightly sized, or if the merger is a fixed grid.
+    ///
+    /// In row major order the rows are dropped off of the end of the buffer in place. In column major order, or when the
+    /// images fill towards the edge the canvas grows at, every image moves, so the canvas is copied to a new buffer first.
+    ///
+    /// # Returns
+    /// * `Ok` - If the canvas was shrunk, or did not need to be.
+    /// * `Err` - A [MergeError::AllocationFailed](MergeError::AllocationFailed) if the canvas has to be copied to a new
+    ///   buffer and there is not enough memory for it. The canvas is left as it was in this case.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, Rgb, BufferedImage};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::with_capacity((100, 100), 10, 95);
+    /// merger.push(&BufferedImage::new(100, 100));
+    /// merger.shrink_to_fit().unwrap();
+    /// assert_eq!(merger.get_canvas().dimensions(), (1000, 100));
+    /// ```
+    pub fn shrink_to_fit(&mut self) -> Result<(), MergeError> {
+        let required_lines = self.num_images.div_ceil(self.images_per_line).max(1);
+        if self.fixed_size || required_lines == self.total_lines {
+            return Ok(());
+        }
+
+        self.resize_canvas(required_lines)?;
+
+        let canvas = std::mem::take(&mut **self.canvas.get_image_mut());
+        let (width, height) = canvas.dimensions();
+        let mut container = canvas.into_raw();
+        container.shrink_to_fit();
+
+        // The buffer was not resized, so it is always the right size.
+        *self.canvas.get_image_mut() = Image::try_from_raw(width, height, container)
+            .unwrap_or_else(|err| panic!("Could not shrink the canvas: {}", err));
+        Ok(())
+    }
+
+    /// Removes an image from the canvas at a given index. Indexing starts at 0 and works left to right, top to bottom. Every
+    /// image after the removed one is shifted one place towards the front of the canvas, and the last place is cleared.
+    ///
+    /// If the removal leaves the last row (or column in column major order) of the canvas empty, it stays allocated and will
+    /// be reused by the next push.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image to remove.
+    ///
+    /// # Returns
+    /// * `Some` - If the image was successfully removed.
+    /// * `None` - If there is no image at the given index.
+    pub fn remove_image(&mut self, index: u32) -> Option<()> {
+        self.remove_image_with_mode(index, RemovalMode::Compact)
+    }
+
+    /// Removes an image from the canvas at a given index, like `remove_image`, but the mode decides whether the images after
+    /// it are shifted towards the front or stay where they are.
+    ///
+    /// With [RemovalMode::Leave](RemovalMode::Leave), the place of the removed image is cleared to the background and left as
+    /// a hole, like the places skipped over by `push_at`. The places up to the highest index holding an image still count as
+    /// occupied, so `get_num_images` only goes down when the last image is removed, and `push` keeps continuing from the
+    /// place after the last image instead of filling the hole. A hole can be filled again with `push_at`.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image to remove.
+    /// * `mode` - Whether the images after the removed one are shifted towards the front, or left in place.
+    ///
+    /// # Returns
+    /// * `Some` - If the image was successfully removed.
+    /// * `None` - If there is no image at the given index.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, RemovalMode, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 8]);
+    ///
+    /// merger.remove_image_with_mode(2, RemovalMode::Leave).unwrap();
+    /// assert_eq!(merger.get_num_images(), 8);
+    ///
+    /// merger.push(&BufferedImage::new(100, 100));
+    /// assert_eq!(merger.last_pasted_index(), Some(8));
+    /// ```
+    pub fn remove_image_with_mode(&mut self, index: u32, mode: RemovalMode) -> Option<()> {
+        if index >= self.num_images {
+            return None;
+        }
+
+        if mode == RemovalMode::Leave {
+            self.clear_image(index);
+            if index + 1 == self.num_images {
+                self.num_images -= 1;
+                self.holes.remove(&index);
+                self.last_pasted_index = self.num_images.checked_sub(1);
+            } else {
+                self.holes.insert(index);
+            }
+            return Some(());
+        }
+
+        // Images only ever move towards the front, so going front to back never overwrites an image before it has been moved.
+        for from in (index + 1)..self.num_images {
+            self.copy_image(from, from - 1);
+        }
+        self.clear_image(self.num_images - 1);
+
+        self.holes = self
+            .holes
+            .iter()
+            .filter(|&&hole| hole != index)
+            .map(|&hole| if hole > index { hole - 1 } else { hole })
+            .collect();
+        self.num_images -= 1;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Some(())
+    }
+
+    /// Removes the images at every given index in a single pass. The images that are kept are shifted towards the front of
+    /// the canvas in their original order, and the places left over at the end are cleared. This leaves the canvas in the
+    /// same state as removing each image with `remove_image`, but every image is moved at most once.
+    ///
+    /// # Arguments
+    /// * `indices` - The indexes of the images to remove, in any order. Duplicates and indexes that do not hold an image
+    ///   are ignored.
+    ///
+    /// # Returns
+    /// The number of images that were removed.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 8]);
+    ///
+    /// assert_eq!(merger.remove_images(&[6, 1, 1, 20]), 2);
+    /// assert_eq!(merger.get_num_images(), 6);
+    /// ```
+    pub fn remove_images(&mut self, indices: &[u32]) -> u32 {
+        let mut removed: Vec<u32> = indices
+            .iter()
+            .copied()
+            .filter(|&index| index < self.num_images)
+            .collect();
+        removed.sort_unstable();
+        removed.dedup();
+
+        let Some(&first) = removed.first() else {
+            return 0;
+        };
+
+        // Like `remove_image`, images only ever move towards the front, so going front to back is always safe.
+        let mut removed_iter = removed.iter().peekable();
+        let mut to = first;
+        for from in first..self.num_images {
+            if removed_iter.next_if_eq(&&from).is_some() {
+                continue;
+            }
+
+            self.copy_image(from, to);
+            to += 1;
+        }
+        for index in to..self.num_images {
+            self.clear_image(index);
+        }
+
+        // The holes that are kept move towards the front by the number of places removed before them.
+        self.holes = self
+            .holes
+            .iter()
+            .filter(|hole| removed.binary_search(hole).is_err())
+            .map(|&hole| hole - removed.partition_point(|&index| index < hole) as u32)
+            .collect();
+        let count = removed.len() as u32;
+        self.num_images -= count;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        count
+    }
+
+    /// Removes every hole from the grid, shifting the images after each one towards the front in their original order, so
+    /// the images fill the places from the first index without any gaps. Holes are left by `push_at` and by removing images
+    /// with [RemovalMode::Leave](RemovalMode::Leave), so edits can be made sparsely and tidied up once at the end. Every
+    /// image is moved at most once, like with `remove_images`.
+    ///
+    /// # Arguments
+    /// * `shrink_to_fit` - Whether to shrink the canvas afterwards with `shrink_to_fit`, dropping the lines emptied by the
+    ///   shift.
+    ///
+    /// # Returns
+    /// * `Ok` - The number of holes that were removed.
+    /// * `Err` - Any of the errors of `shrink_to_fit`. The holes are removed even in this case, only the canvas keeps its
+    ///   size.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Merger, GrowableMerger, BufferedImage, RemovalMode, Rgb};
+    ///
+    /// let mut merger: GrowableMerger<Rgb<u8>> = GrowableMerger::new((100, 100), 5);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 4]);
+    /// merger.push_at(7, &BufferedImage::new(100, 100)).unwrap();
+    /// merger.remove_image_with_mode(1, RemovalMode::Leave).unwrap();
+    /// assert_eq!(merger.get_num_images(), 8);
+    ///
+    /// assert_eq!(merger.compact(true).unwrap(), 4);
+    /// assert_eq!(merger.get_num_images(), 4);
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 100));
+    /// ```
+    pub fn compact(&mut self, shrink_to_fit: bool) -> Result<u32, MergeError> {
+        let holes: Vec<u32> = self.holes.iter().copied().collect();
+        let count = self.remove_images(&holes);
+        if shrink_to_fit {
+            self.shrink_to_fit()?;
+        }
+        Ok(count)
+    }
+}
+
+impl<P> Merger<P, Vec<P::Subpixel>> for GrowableMerger<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    fn get_canvas(&self) -> &BufferedImage<P> {
+        &self.canvas
+    }
+
+    fn into_canvas(self) -> BufferedImage<P> {
+        self.canvas.into_inner()
+    }
+
+    fn push(&mut self, image: &BufferedImage<P>) {
+        if let Err(err) = self.try_push(image) {
+            panic!("{}", err);
+        }
+    }
+
+    fn bulk_push(&mut self, images: &[&BufferedImage<P>]) {
+        if let Err(err) = self.try_bulk_push(images) {
+            panic!("{}", err);
+        }
+    }
+}
+
+impl<P> GrowableMerger<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    /// Pushes a slice of images onto the canvas, growing the canvas once to fit all of them. Unlike `bulk_push`,
+    /// this method returns an error instead of panicking.
+    ///
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas. Their dimensions must match the image dimensions of the merger, unless
+    ///   the merger was set to resize images on push with `with_resize_on_push`.
+    ///
+    /// # Returns
+    /// * `Ok` - If every image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if any of the images is not the right size,
+    ///   a [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the merger is a fixed grid without enough free cells
+    ///   left for all of the images, a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would have to
+    ///   grow past its maximum dimensions, or a [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not
+    ///   enough memory to grow it. Nothing is pasted in any of these cases.
+    pub fn try_bulk_push(&mut self, images: &[&BufferedImage<P>]) -> Result<(), MergeError> {
+        for image in images {
+            self.check_fittable(image)?;
+        }
+
+        // Figure out how many rows are needed to hold every image up front so the canvas only has to grow once. Flushing can
+        // not be undone, so the rows are checked against what is left after flushing before anything is flushed.
+        let lines = u32::try_from(images.len())
+            .ok()
+            .and_then(|len| self.num_images.checked_add(len))
+            .ok_or(MergeError::CanvasTooLarge {
+                max_dimensions: self.max_canvas_dimensions,
+            })?
+            .div_ceil(self.images_per_line);
+        let flushed_rows = self.rows_to_flush(lines);
+        let required_lines = lines - flushed_rows;
+        if required_lines > self.total_lines {
+            self.check_can_grow(required_lines)?;
+        }
+        if flushed_rows > 0 {
+            self.flush_completed_rows();
+        }
+
+        let next_index = self.next_index();
+        let fitted: Vec<Option<BufferedImage<P>>> = images
+            .iter()
+            .zip(next_index..)
+            .map(|(image, index)| self.fit_image(image, index))
+            .collect();
+        let images: Vec<&BufferedImage<P>> = images
+            .iter()
+            .zip(&fitted)
+            .map(|(image, fitted)| fitted.as_ref().unwrap_or(image))
+            .collect();
+
+        if required_lines > self.total_lines {
+            log_event!(
+                debug,
+                "Growing the canvas by {} lines to fit {} more images.",
+                required_lines - self.total_lines,
+                images.len()
+            );
+            self.grow_canvas_by(required_lines - self.total_lines)?;
+        }
+
+        let first_index = self.next_index();
+        match self.progress_callback.take() {
+            None => self.paste_all(&images, first_index),
+            Some(mut callback) => {
+                // Paste the images in batches that are large enough to keep every thread busy, reporting after each one.
+                let total = images.len() as u32;
+                let batch_size = parallel::current_num_threads().max(self.images_per_line as usize);
+                let mut done = 0;
+                for batch in images.chunks(batch_size) {
+                    self.paste_all(batch, first_index + done);
+                    done += batch.len() as u32;
+                    callback(done, total);
+                }
+                self.progress_callback = Some(callback);
+            }
+        }
+
+        self.num_images += images.len() as u32;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Pastes the given images onto the canvas, starting at the given index. The canvas is split into bands of rows that each
+    /// hold one row of places, and the images are copied into every band at once when the `rayon` feature is enabled.
+    fn paste_all(&mut self, images: &[&BufferedImage<P>], first_index: u32) {
+        let header_height = self.band_heights().0 as usize;
+        let band_height =
+            (self.cell_dimensions().1 + self.padding.map_or(0, |padding| padding.y)) as usize;
+
+        // Sort the images into the bands holding their places, with their positions relative to the top of the band.
+        let mut bands: Vec<Vec<(&BufferedImage<P>, u32, u32)>> = Vec::new();
+        for (image, index) in images.iter().zip(first_index..) {
+            let (x, y) = self.get_paste_coordinates_unchecked(index);
+            let (band, y) = (
+                (y as usize - header_height) / band_height,
+                (y as usize - header_height) % band_height,
+            );
+            if bands.len() <= band {
+                bands.resize_with(band + 1, Vec::new);
+            }
+            bands[band].push((image, x, y as u32));
+        }
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let canvas_row_len = self.canvas.width() as usize * channels;
+        let image_row_len = self.image_dimensions.0 as usize * channels;
+        let buffer: &mut [P::Subpixel] = self.canvas.get_image_mut();
+        let (_, grid) = buffer.split_at_mut(header_height * canvas_row_len);
+        grid.par_chunks_mut(band_height * canvas_row_len)
+            .zip(bands)
+            .for_each(|(rows, band)| {
+                for (image, x, y) in band {
+                    let start = x as usize * channels;
+                    for (canvas_row, image_row) in rows
+                        .chunks_exact_mut(canvas_row_len)
+                        .skip(y as usize)
+                        .zip(image.chunks_exact(image_row_len))
+                    {
+                        canvas_row[start..start + image_row_len].copy_from_slice(image_row);
+                    }
+                }
+            });
+
+        // The bands are pasted in any order, so the images are logged once all of them are on the canvas.
+        #[cfg(feature = "log")]
+        for index in first_index..first_index + images.len() as u32 {
+            let (x, y) = self.get_paste_coordinates_unchecked(index);
+            log_event!(
+                trace,
+                "Pasted the image at index {} at ({}, {}).",
+                index,
+                x,
+                y
+            );
+        }
+    }
+}
+
+impl<P> ResizableMerger<P> for GrowableMerger<P>
+where
+    P: Pixel + Sync + Send + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    fn push_resized(&mut self, image: &BufferedImage<P>) {
+        let (width, height) = self.image_dimensions;
+        let resized = resize_nearest_neighbor(image, width, height);
+        self.push(&resized);
+    }
+
+    fn bulk_push_resized(&mut self, images: &[&BufferedImage<P>]) {
+        // Resize all the images in parallel then push them
+        let resized_images: Vec<BufferedImage<P>> = images
+            .into_par_iter()
+            .map(|image| {
+                let (width, height) = self.image_dimensions;
+                resize_nearest_neighbor(image, width, height)
+            })
+            .collect();
+
+        // Convert Vec<T> to [&T] for the bulk push method
+        let resized_images_ref: Vec<&BufferedImage<P>> = resized_images.iter().collect();
+
+        self.bulk_push(&resized_images_ref);
+    }
+}
+
+/// Serialization of a [GrowableMerger](GrowableMerger), so a merger can be saved and picked up again later. The canvas is
+/// stored as its raw subpixels, along with the layout of the grid. Progress callbacks can not be serialized, so they are not
+/// kept.
+#[cfg(feature = "serde")]
+mod serialization {
+    use super::{
+        Background, CellAlignment, FillOrder, GrowableMerger, GrowthStrategy, HorizontalDirection,
+        Padding, VerticalDirection,
+    };
+    use crate::{cell::ImageCell, BufferedImage, Image, MergeError};
+    use image::{imageops::FilterType, Pixel};
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct State<S> {
+        image_dimensions: (u32, u32),
+        num_images: u32,
+        holes: Vec<u32>,
+        images_per_line: u32,
+        total_lines: u32,
+        fill_order: FillOrder,
+        #[serde(default)]
+        fill_directions: (HorizontalDirection, VerticalDirection),
+        fixed_size: bool,
+        growth_strategy: GrowthStrategy,
+        max_canvas_dimensions: Option<(u32, u32)>,
+        padding: Option<Padding>,
+        background: Option<BackgroundState<S>>,
+        border: Option<(u32, Vec<S>)>,
+        caption_height: u32,
+        tight_last_row: bool,
+        header: Option<BandState<S>>,
+        footer: Option<BandState<S>>,
+        resize_filter: Option<u8>,
+        preserve_aspect_ratio: bool,
+        cell_alignment: Option<CellAlignment>,
+        canvas: Vec<S>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct BandState<S> {
+        width: u32,
+        height: u32,
+        pixels: Vec<S>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum BackgroundState<S> {
+        Solid(Vec<S>),
+        Checker { a: Vec<S>, b: Vec<S>, size: u32 },
+    }
+
+    fn filter_to_index(filter: FilterType) -> u8 {
+        match filter {
+            FilterType::Nearest => 0,
+            FilterType::Triangle => 1,
+            FilterType::CatmullRom => 2,
+            FilterType::Gaussian => 3,
+            FilterType::Lanczos3 => 4,
+        }
+    }
+
+    fn filter_from_index(index: u8) -> Option<FilterType> {
+        match index {
+            0 => Some(FilterType::Nearest),
+            1 => Some(FilterType::Triangle),
+            2 => Some(FilterType::CatmullRom),
+            3 => Some(FilterType::Gaussian),
+            4 => Some(FilterType::Lanczos3),
+            _ => None,
+        }
+    }
+
+    fn band_state<P: Pixel>(band: &BufferedImage<P>) -> BandState<P::Subpixel> {
+        BandState {
+            width: band.width(),
+            height: band.height(),
+            pixels: band.as_raw().clone(),
+        }
+    }
+
+    fn band_from_state<P: Pixel>(
+        state: BandState<P::Subpixel>,
+    ) -> Result<BufferedImage<P>, MergeError> {
+        Image::try_from_raw(state.width, state.height, state.pixels)
+    }
+
+    fn pixel_from_channels<P: Pixel>(channels: &[P::Subpixel]) -> Option<P> {
+        (channels.len() == <P as Pixel>::CHANNEL_COUNT as usize).then(|| *P::from_slice(channels))
+    }
+
+    impl<P> Serialize for GrowableMerger<P>
+    where
+        P: Pixel,
+        <P as Pixel>::Subpixel: Sync + Send + Serialize,
+    {
+        fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+            State {
+                image_dimensions: self.image_dimensions,
+                num_images: self.num_images,
+                holes: self.holes.iter().copied().collect(),
+                images_per_line: self.images_per_line,
+                total_lines: self.total_lines,
+                fill_order: self.fill_order,
+                fill_directions: self.fill_directions,
+                fixed_size: self.fixed_size,
+                growth_strategy: self.growth_strategy,
+                max_canvas_dimensions: self.max_canvas_dimensions,
+                padding: self.padding,
+                background: self.background.map(|background| match background {
+                    Background::Solid(color) => BackgroundState::Solid(color.channels().to_vec()),
+                    Background::Checker { a, b, size } => BackgroundState::Checker {
+                        a: a.channels().to_vec(),
+                        b: b.channels().to_vec(),
+                        size,
+                    },
+                }),
+                border: self
+                    .border
+                    .map(|(thickness, color)| (thickness, color.channels().to_vec())),
+                caption_height: self.caption_height,
+                tight_last_row: self.tight_last_row,
+                header: self.header.as_ref().map(band_state),
+                footer: self.footer.as_ref().map(band_state),
+                resize_filter: self.resize_filter.map(filter_to_index),
+                preserve_aspect_ratio: self.preserve_aspect_ratio,
+                cell_alignment: self.cell_alignment,
+                canvas: self.canvas.as_raw().clone(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, P> Deserialize<'de> for GrowableMerger<P>
+    where
+        P: Pixel + Sync + 'static,
+        <P as Pixel>::Subpixel: Sync + Send + Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let state = State::<P::Subpixel>::deserialize(deserializer)?;
+
+            if state.images_per_line == 0 || state.total_lines == 0 {
+                return Err(D::Error::custom(
+                    "a merger must have at least one image per row",
+                ));
+            }
+            if state.image_dimensions.0 == 0 || state.image_dimensions.1 == 0 {
+                return Err(D::Error::custom(
+                    "a merger can not hold images with a width or height of zero",
+                ));
+            }
+            let capacity = state.images_per_line.checked_mul(state.total_lines);
+            if capacity.is_none_or(|capacity| state.num_images > capacity) {
+                return Err(D::Error::custom(
+                    "the canvas holds more images than fit on it",
+                ));
+            }
+            if state.holes.iter().any(|&hole| hole >= state.num_images) {
+                return Err(D::Error::custom("a hole lies past the last image"));
+            }
+
+            let invalid_background = || D::Error::custom("invalid background color");
+            let background = match state.background {
+                Some(BackgroundState::Solid(channels)) => Some(Background::Solid(
+                    pixel_from_channels(&channels).ok_or_else(invalid_background)?,
+                )),
+                Some(BackgroundState::Checker { size: 0, .. }) => {
+                    return Err(D::Error::custom(
+                        "the squares of a checkerboard background can not be empty",
+                    ))
+                }
+                Some(BackgroundState::Checker { a, b, size }) => Some(Background::Checker {
+                    a: pixel_from_channels(&a).ok_or_else(invalid_background)?,
+                    b: pixel_from_channels(&b).ok_or_else(invalid_background)?,
+                    size,
+                }),
+                None => None,
+            };
+            let border = match state.border {
+                Some((thickness, channels)) => Some((
+                    thickness,
+                    pixel_from_channels(&channels)
+                        .ok_or_else(|| D::Error::custom("invalid border color"))?,
+                )),
+                None => None,
+            };
+            let resize_filter = match state.resize_filter {
+                Some(index) => Some(
+                    filter_from_index(index)
+                        .ok_or_else(|| D::Error::custom("invalid resize filter"))?,
+                ),
+                None => None,
+            };
+
+            let mut merger = GrowableMerger {
+                canvas: ImageCell::new(Image::new(0, 0)),
+                image_dimensions: state.image_dimensions,
+                num_images: state.num_images,
+                images_per_line: state.images_per_line,
+                last_pasted_index: state.num_images.checked_sub(1),
+                holes: state.holes.into_iter().collect(),
+                total_lines: state.total_lines,
+                fill_order: state.fill_order,
+                fill_directions: state.fill_directions,
+                fixed_size: state.fixed_size,
+                growth_strategy: state.growth_strategy,
+                max_canvas_dimensions: state.max_canvas_dimensions,
+                padding: state.padding,
+                background,
+                border,
+                caption_height: state.caption_height,
+                tight_last_row: state.tight_last_row,
+                header: state
+                    .header
+                    .map(band_from_state)
+                    .transpose()
+                    .map_err(D::Error::custom)?,
+                footer: state
+                    .footer
+                    .map(band_from_state)
+                    .transpose()
+                    .map_err(D::Error::custom)?,
+                resize_filter,
+                preserve_aspect_ratio: state.preserve_aspect_ratio,
+                cell_alignment: state.cell_alignment,
+                progress_callback: None,
+                memory_budget: None,
+                flush_callback: None,
+                flushed_rows: 0,
+            };
+
+            let (width, height) = merger
+                .checked_canvas_dimensions_for(state.total_lines)
+                .ok_or_else(|| D::Error::custom("the canvas is too large"))?;
+            let canvas =
+                Image::try_from_raw(width, height, state.canvas).map_err(D::Error::custom)?;
+            merger.canvas = ImageCell::new(canvas);
+            Ok(merger)
+        }
+    }
+}
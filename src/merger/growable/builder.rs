@@ -0,0 +1,279 @@
+use super::{FlushCallback, GrowableMerger, ProgressCallback};
+use crate::{
+    merger::{
+        core::check_layout, Background, CellAlignment, FillOrder, GrowthStrategy,
+        HorizontalDirection, MergeError, Padding, VerticalDirection,
+    },
+    BufferedImage,
+};
+
+use image::{imageops::FilterType, ImageBuffer, Pixel};
+
+/// A builder that configures every option of a [GrowableMerger](GrowableMerger) in one place, and checks that the options
+/// work together before the canvas is allocated. The canvas is only allocated once, when the merger is built. The image
+/// dimensions and the number of images per row must be set, every other option is optional.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{Merger, GrowableMerger, GrowthStrategy, Padding, Rgb};
+///
+/// let merger: GrowableMerger<Rgb<u8>> = GrowableMerger::builder()
+///     .image_dimensions((100, 100))
+///     .images_per_row(5)
+///     .capacity(15)
+///     .padding(Padding { x: 10, y: 10 })
+///     .growth_strategy(GrowthStrategy::Double)
+///     .build()
+///     .unwrap();
+/// assert_eq!(merger.get_canvas().dimensions(), (540, 320));
+/// ```
+pub struct GrowableMergerBuilder<P: Pixel> {
+    image_dimensions: Option<(u32, u32)>,
+    images_per_row: Option<u32>,
+    capacity: u32,
+    padding: Option<Padding>,
+    background: Option<Background<P>>,
+    border: Option<(u32, P)>,
+    caption_height: u32,
+    tight_last_row: bool,
+    header: Option<BufferedImage<P>>,
+    footer: Option<BufferedImage<P>>,
+    fill_order: FillOrder,
+    fill_directions: (HorizontalDirection, VerticalDirection),
+    growth_strategy: GrowthStrategy,
+    max_canvas_dimensions: Option<(u32, u32)>,
+    resize: Option<(FilterType, bool)>,
+    cell_alignment: Option<CellAlignment>,
+    progress_callback: Option<ProgressCallback>,
+    memory_budget: Option<(usize, FlushCallback<P>)>,
+}
+
+impl<P> Default for GrowableMergerBuilder<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> GrowableMergerBuilder<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    /// Constructs a new builder with none of the options set.
+    pub fn new() -> Self {
+        Self {
+            image_dimensions: None,
+            images_per_row: None,
+            capacity: 0,
+            padding: None,
+            background: None,
+            border: None,
+            caption_height: 0,
+            tight_last_row: false,
+            header: None,
+            footer: None,
+            fill_order: FillOrder::RowMajor,
+            fill_directions: Default::default(),
+            growth_strategy: GrowthStrategy::SingleRow,
+            max_canvas_dimensions: None,
+            resize: None,
+            cell_alignment: None,
+            progress_callback: None,
+            memory_budget: None,
+        }
+    }
+
+    /// Sets the dimensions of the images being pasted (images must be a uniform size). This must be set.
+    pub fn image_dimensions(mut self, image_dimensions: (u32, u32)) -> Self {
+        self.image_dimensions = Some(image_dimensions);
+        self
+    }
+
+    /// Sets the number of images per row, or per column when filling in column major order. This must be set.
+    pub fn images_per_row(mut self, images_per_row: u32) -> Self {
+        self.images_per_row = Some(images_per_row);
+        self
+    }
+
+    /// Sets the number of images the canvas can hold before it needs to grow, like
+    /// [GrowableMerger::with_capacity](GrowableMerger::with_capacity). The canvas always holds at least one row.
+    pub fn capacity(mut self, expected_images: u32) -> Self {
+        self.capacity = expected_images;
+        self
+    }
+
+    /// Sets the padding between images, like [GrowableMerger::with_padding](GrowableMerger::with_padding).
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Sets the background of the canvas, like
+    /// [GrowableMerger::with_background_pattern](GrowableMerger::with_background_pattern).
+    pub fn background(mut self, background: Background<P>) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// Sets the border drawn around every place on the canvas, like [GrowableMerger::with_borders](GrowableMerger::with_borders).
+    pub fn borders(mut self, thickness: u32, color: P) -> Self {
+        self.border = Some((thickness, color));
+        self
+    }
+
+    /// Reserves a strip below every place for a caption, like
+    /// [GrowableMerger::with_caption_height](GrowableMerger::with_caption_height).
+    pub fn caption_height(mut self, caption_height: u32) -> Self {
+        self.caption_height = caption_height;
+        self
+    }
+
+    /// Adds a header band above the grid, like [GrowableMerger::with_header](GrowableMerger::with_header).
+    pub fn header(mut self, header: BufferedImage<P>) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Adds a footer band below the grid, like [GrowableMerger::with_footer](GrowableMerger::with_footer).
+    pub fn footer(mut self, footer: BufferedImage<P>) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    /// Makes trimmed copies of the canvas crop the empty places at the end of a lone partial row, like
+    /// [GrowableMerger::with_tight_last_row](GrowableMerger::with_tight_last_row).
+    pub fn tight_last_row(mut self, tight_last_row: bool) -> Self {
+        self.tight_last_row = tight_last_row;
+        self
+    }
+
+    /// Sets the order images are placed onto the canvas in, like
+    /// [GrowableMerger::with_fill_order](GrowableMerger::with_fill_order).
+    pub fn fill_order(mut self, fill_order: FillOrder) -> Self {
+        self.fill_order = fill_order;
+        self
+    }
+
+    /// Sets the directions images advance in along the rows and the columns of the canvas, like
+    /// [GrowableMerger::with_fill_directions](GrowableMerger::with_fill_directions).
+    pub fn fill_directions(
+        mut self,
+        horizontal: HorizontalDirection,
+        vertical: VerticalDirection,
+    ) -> Self {
+        self.fill_directions = (horizontal, vertical);
+        self
+    }
+
+    /// Sets how much the canvas grows by when it is full, like
+    /// [GrowableMerger::with_growth_strategy](GrowableMerger::with_growth_strategy).
+    pub fn growth_strategy(mut self, growth_strategy: GrowthStrategy) -> Self {
+        self.growth_strategy = growth_strategy;
+        self
+    }
+
+    /// Limits how large the canvas is allowed to grow, like
+    /// [GrowableMerger::with_max_canvas_dimensions](GrowableMerger::with_max_canvas_dimensions).
+    pub fn max_canvas_dimensions(mut self, max_canvas_dimensions: (u32, u32)) -> Self {
+        self.max_canvas_dimensions = Some(max_canvas_dimensions);
+        self
+    }
+
+    /// Makes the merger resize pushed images that are the wrong size, like
+    /// [GrowableMerger::with_resize_on_push](GrowableMerger::with_resize_on_push).
+    pub fn resize_on_push(mut self, filter: FilterType, preserve_aspect_ratio: bool) -> Self {
+        self.resize = Some((filter, preserve_aspect_ratio));
+        self
+    }
+
+    /// Sets where undersized images are positioned within their place, like
+    /// [GrowableMerger::with_cell_alignment](GrowableMerger::with_cell_alignment).
+    pub fn cell_alignment(mut self, alignment: CellAlignment) -> Self {
+        self.cell_alignment = Some(alignment);
+        self
+    }
+
+    /// Sets a callback that is called as bulk operations make progress, like
+    /// [GrowableMerger::set_progress_callback](GrowableMerger::set_progress_callback).
+    pub fn progress_callback<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(u32, u32) + Send + Sync + 'static,
+    {
+        self.progress_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Caps how much memory the canvas may take up, flushing completed rows to the given callback to make room, like
+    /// [GrowableMerger::with_memory_budget](GrowableMerger::with_memory_budget).
+    pub fn memory_budget<F>(mut self, bytes: usize, flush: F) -> Self
+    where
+        F: FnMut(ImageBuffer<P, Vec<P::Subpixel>>) + Send + Sync + 'static,
+    {
+        self.memory_budget = Some((bytes, Box::new(flush)));
+        self
+    }
+
+    /// Checks the options and builds the merger, allocating its canvas.
+    ///
+    /// # Returns
+    /// * `Ok` - The configured merger.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if the image dimensions or the number
+    ///   of images per row were not set, are zero, the background is a checkerboard with a square size of zero, or a memory
+    ///   budget is set for a merger that fills in column major order or bottom to top, or is too small to hold a single
+    ///   row of images, or a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if a canvas holding the given capacity would be larger than
+    ///   the maximum canvas dimensions or the memory budget, or too large to fit in memory, or a
+    ///   [MergeError::AllocationFailed](MergeError::AllocationFailed) if there is not enough memory for the canvas.
+    pub fn build(self) -> Result<GrowableMerger<P>, MergeError> {
+        let image_dimensions = self
+            .image_dimensions
+            .ok_or(MergeError::InvalidConfiguration(
+                "the image dimensions must be set",
+            ))?;
+        let images_per_row = self.images_per_row.ok_or(MergeError::InvalidConfiguration(
+            "the number of images per row must be set",
+        ))?;
+        check_layout(image_dimensions, images_per_row)?;
+
+        let (resize_filter, preserve_aspect_ratio) = match self.resize {
+            Some((filter, preserve_aspect_ratio)) => (Some(filter), preserve_aspect_ratio),
+            None => (None, false),
+        };
+        let total_lines = self.capacity.div_ceil(images_per_row).max(1);
+        let mut merger = GrowableMerger::unallocated(image_dimensions, images_per_row, total_lines);
+        merger.fill_order = self.fill_order;
+        merger.fill_directions = self.fill_directions;
+        merger.growth_strategy = self.growth_strategy;
+        merger.max_canvas_dimensions = self.max_canvas_dimensions;
+        merger.padding = self.padding;
+        merger.background = self.background;
+        merger.border = self.border;
+        merger.caption_height = self.caption_height;
+        merger.tight_last_row = self.tight_last_row;
+        merger.header = self.header;
+        merger.footer = self.footer;
+        merger.resize_filter = resize_filter;
+        merger.preserve_aspect_ratio = preserve_aspect_ratio;
+        merger.cell_alignment = self.cell_alignment;
+        merger.progress_callback = self.progress_callback;
+        if let Some((bytes, flush)) = self.memory_budget {
+            merger.memory_budget = Some(bytes);
+            merger.flush_callback = Some(flush);
+        }
+
+        // The options are checked the same way the `with_` methods of the merger check them, before anything is allocated.
+        merger.check_background()?;
+        merger.check_fill_order()?;
+        merger.check_memory_budget()?;
+        merger.check_fits(merger.total_lines)?;
+        merger.try_allocate_canvas()?;
+        Ok(merger)
+    }
+}
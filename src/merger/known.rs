@@ -1,13 +1,16 @@
-use super::core::{Merger, Padding, Point};
+use super::{
+    core::{check_layout, Layout, Merger, Padding, Point, RowMajorLayout},
+    error::MergeError,
+};
 use crate::{
     cell::ImageCell,
     functions::{paste, resize_nearest_neighbor},
+    parallel::prelude::*,
     BufferedImage, Image, ResizableMerger,
 };
 
 use image::Pixel;
 use num_traits::Zero;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::ops::DerefMut;
 
 /// A known size merger that allows you to paste images onto a canvas. This merger is useful when you already know the size
@@ -17,6 +20,7 @@ use std::ops::DerefMut;
 /// # Type Parameters
 /// * `P` - The pixel type of the underlying image.
 /// * `Container` - The underlying image buffer type. This must be dereferenceable to a slice of the underlying image's subpixels.
+/// * `L` - The [Layout](Layout) that decides where images are placed, images are placed in rows by default.
 ///
 /// # Example
 /// ```
@@ -26,19 +30,20 @@ use std::ops::DerefMut;
 /// let image = Image::new(100, 100);
 /// merger.bulk_push(&[&image, &image, &image, &image, &image]);
 /// ```
-pub struct KnownSizeMerger<P, Container>
+pub struct KnownSizeMerger<P, Container, L = RowMajorLayout>
 where
     P: Pixel,
     <P as Pixel>::Subpixel: Sync,
     Container: DerefMut<Target = [P::Subpixel]> + Sync,
+    L: Layout,
 {
     canvas: ImageCell<P, image::ImageBuffer<P, Container>>,
     image_dimensions: (u32, u32), // The dimensions of the images being pasted (images must be a uniform size)
     num_images: u32,              // The number of images that have been pasted to the canvas
     images_per_row: u32,          // The number of pages per row.
-    last_pasted_index: i32, // The index of the last pasted image, starts at -1 if not images have been pasted.
-    total_rows: u32,        // The total number of rows currently on the canvas.
-    padding: Option<Padding>,
+    last_pasted_index: Option<u32>, // The index of the last pasted image, `None` if no images have been pasted.
+    total_rows: u32,                // The total number of rows currently on the canvas.
+    layout: L,
 }
 
 impl<P, Container> KnownSizeMerger<P, Container>
@@ -55,11 +60,12 @@ where
     /// * `total_images` - The total number of images to be in the final canvas.
     /// * `padding` - The padding between images, or None for no padding.
     /// * `container` - The container to use for the underlying canvas. This container must be big enough to hold all the potential images
-    /// that will be pasted to the canvas.
+    ///   that will be pasted to the canvas.
     ///
     /// # Returns
     /// * `Some` - If the merger was successfully created.
-    /// * `None` - If the merger could not be created. This will happen if the container is not large enough to fit all the images.
+    /// * `None` - If the merger could not be created. This will happen if the container is not large enough to fit all the images,
+    ///   or if `images_per_row` or either of the image dimensions is zero.
     ///
     /// # Example
     /// ```
@@ -75,32 +81,39 @@ where
         padding: Option<Padding>,
         container: Container,
     ) -> Option<Self> {
-        let total_rows = (total_images + images_per_row - 1) / images_per_row;
-
-        let image_gaps_x = (images_per_row - 1) * padding.as_ref().map(|p| p.x).unwrap_or(0);
-        let image_gaps_y = (total_rows - 1) * padding.as_ref().map(|p| p.y).unwrap_or(0);
+        check_layout(image_dimensions, images_per_row).ok()?;
+        let layout = RowMajorLayout { padding };
+        let (width, height) = layout.canvas_size(total_images, image_dimensions, images_per_row);
 
-        Image::new_from_raw(
-            (image_dimensions.0 * images_per_row) + image_gaps_x,
-            (image_dimensions.1 * total_rows) + image_gaps_y,
-            container,
-        )
-        .map(|canvas| Self {
+        Image::new_from_raw(width, height, container).map(|canvas| Self {
             canvas: ImageCell::new(canvas),
             image_dimensions,
             num_images: 0,
             images_per_row,
-            last_pasted_index: -1,
-            total_rows,
-            padding,
+            last_pasted_index: None,
+            total_rows: total_images.div_ceil(images_per_row),
+            layout,
         })
     }
+}
 
+impl<P, Container, L> KnownSizeMerger<P, Container, L>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+    L: Layout,
+{
     /// Returns the number of images that have been pasted to the canvas.
     pub fn get_num_images(&self) -> u32 {
         self.num_images
     }
 
+    /// Returns the index of the most recently pasted image, or `None` if no images have been pasted.
+    pub fn last_pasted_index(&self) -> Option<u32> {
+        self.last_pasted_index
+    }
+
     /// Returns the dimensions, (x, y), of the images being pasted to the canvas.
     pub fn get_image_dimensions(&self) -> (u32, u32) {
         self.image_dimensions
@@ -111,25 +124,108 @@ where
         (self.images_per_row * self.total_rows) - self.num_images
     }
 
+    /// Returns the layout that decides where images are placed on the canvas.
+    pub fn get_layout(&self) -> &L {
+        &self.layout
+    }
+
     fn get_paste_coordinates_unchecked(&self, index: u32) -> (u32, u32) {
-        let offset_x = index % self.images_per_row;
-        let offset_y = index / self.images_per_row;
+        self.layout
+            .coordinates(index, self.image_dimensions, self.images_per_row)
+    }
 
-        let padding_x = self.padding.as_ref().map(|p| p.x).unwrap_or(0) * offset_x;
-        let padding_y = self.padding.as_ref().map(|p| p.y).unwrap_or(0) * offset_y;
+    /// Returns the index the next image is pasted at.
+    fn next_index(&self) -> u32 {
+        self.last_pasted_index.map_or(0, |index| index + 1)
+    }
 
-        let x = (offset_x * self.image_dimensions.0) + padding_x;
-        let y = (offset_y * self.image_dimensions.1) + padding_y;
+    fn get_next_paste_coordinates(&self) -> Result<(u32, u32), MergeError> {
+        if self.additional_space() == 0 {
+            return Err(MergeError::OutOfCapacity {
+                capacity: self.images_per_row * self.total_rows,
+            });
+        }
 
-        (x, y)
+        Ok(self.get_paste_coordinates_unchecked(self.next_index()))
     }
 
-    fn get_next_paste_coordinates(&mut self) -> (u32, u32) {
-        if self.additional_space() == 0 {
-            panic!("No more space on the canvas!");
+    fn check_dimensions(
+        &self,
+        image: &Image<P, image::ImageBuffer<P, Container>>,
+    ) -> Result<(), MergeError> {
+        if image.dimensions() != self.image_dimensions {
+            return Err(MergeError::DimensionMismatch {
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Pushes an image onto the canvas. Unlike `push`, this method checks the dimensions of the image and the space left on
+    /// the canvas first, and returns an error instead of panicking.
+    ///
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. Its dimensions must match the image dimensions of the merger.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the right size, or a
+    ///   [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if every place on the canvas is filled. Nothing is pasted in
+    ///   either case.
+    pub fn try_push(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, Container>>,
+    ) -> Result<(), MergeError> {
+        self.check_dimensions(image)?;
+        let (x, y) = self.get_next_paste_coordinates()?;
+
+        paste(&self.canvas, image, Point { x, y });
+
+        self.num_images += 1;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
+    }
+
+    /// Pushes a set of images onto the canvas in parallel. Unlike `bulk_push`, this method checks the dimensions of the images
+    /// and the space left on the canvas first, and returns an error instead of panicking.
+    ///
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas. Their dimensions must match the image dimensions of the merger.
+    ///
+    /// # Returns
+    /// * `Ok` - If every image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if any of the images is not the right size,
+    ///   or a [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if there is not enough space left on the canvas for all
+    ///   of them. Nothing is pasted in either case.
+    pub fn try_bulk_push(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, Container>>],
+    ) -> Result<(), MergeError> {
+        if self.additional_space() < images.len() as u32 {
+            return Err(MergeError::OutOfCapacity {
+                capacity: self.images_per_row * self.total_rows,
+            });
+        }
+        for image in images {
+            self.check_dimensions(image)?;
         }
 
-        self.get_paste_coordinates_unchecked((self.last_pasted_index + 1) as u32)
+        (0..images.len()).into_par_iter().for_each(|index| {
+            let image = images[index];
+
+            // The image coordinates can easily be calculated by using the last_pasted_index
+            // and making the calculations ourselves.
+            let offset_index = self.next_index() + index as u32;
+
+            let (x, y) = self.get_paste_coordinates_unchecked(offset_index);
+            paste(&self.canvas, image, Point { x, y });
+        });
+
+        self.num_images += images.len() as u32;
+        self.last_pasted_index = self.num_images.checked_sub(1);
+        Ok(())
     }
 
     /// Removes an image from the canvas at the given index. Indices start at 0 and work left to right, top to bottom. Most of the time
@@ -139,17 +235,13 @@ where
     /// # Arguments
     /// * `index` - The index of the image to remove.
     /// * `container` - The container to use to replace the image. The container must be the same size as the image being removed,
-    /// thus, the container must be the same size as the image dimensions.
+    ///   thus, the container must be the same size as the image dimensions.
     ///
     /// # Returns
     /// * `Some` - If the image was successfully removed.
     /// * `None` - If the image could not be removed. This will happen if the container is not large enough to fit the image.
     pub fn remove_image_raw(&mut self, index: u32, container: Container) -> Option<()> {
-        let offset_x = index % self.images_per_row;
-        let offset_y = index / self.images_per_row;
-
-        let x = offset_x * self.image_dimensions.0;
-        let y = offset_y * self.image_dimensions.1;
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
 
         let black_image =
             Image::new_from_raw(self.image_dimensions.0, self.image_dimensions.1, container);
@@ -176,63 +268,158 @@ where
     /// * `images_per_row` - The number of images per row.
     /// * `total_images` - The total number of images to be in the final canvas.
     /// * `padding` - The padding between images, or None for no padding.
+    ///
+    /// # Panics
+    /// This function will panic if `images_per_row` or either of the image dimensions is zero.
     pub fn new(
         image_dimensions: (u32, u32),
         images_per_row: u32,
         total_images: u32,
         padding: Option<Padding>,
     ) -> Self {
-        let total_rows = (total_images + images_per_row - 1) / images_per_row;
+        Self::try_new(image_dimensions, images_per_row, total_images, padding)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        let image_gaps_x = (images_per_row - 1) * padding.as_ref().map(|p| p.x).unwrap_or(0);
-        let image_gaps_y = (total_rows - 1) * padding.as_ref().map(|p| p.y).unwrap_or(0);
+    /// Constructs a new KnownSizeMerger like `new`, but returns an error instead of panicking if it can not be laid out.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    ///
+    /// # Returns
+    /// * `Ok` - The merger.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` or either of the
+    ///   image dimensions is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{KnownSizeMerger, MergeError, Rgb};
+    ///
+    /// let merger = KnownSizeMerger::<Rgb<u8>, _>::try_new((100, 100), 0, 10, None);
+    /// assert!(matches!(merger, Err(MergeError::InvalidConfiguration(_))));
+    /// ```
+    pub fn try_new(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        padding: Option<Padding>,
+    ) -> Result<Self, MergeError> {
+        Self::try_with_layout(
+            image_dimensions,
+            images_per_row,
+            total_images,
+            RowMajorLayout { padding },
+        )
+    }
+}
 
-        let canvas = Image::new(
-            (image_dimensions.0 * images_per_row) + image_gaps_x,
-            (image_dimensions.1 * total_rows) + image_gaps_y,
-        );
+impl<P, L> KnownSizeMerger<P, Vec<P::Subpixel>, L>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    L: Layout,
+{
+    /// Constructs a new KnownSizeMerger that places images with the given [Layout](Layout) instead of in rows. The canvas is
+    /// sized by the layout to hold every image.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row, which is passed on to the layout.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `layout` - The layout that decides where images are placed.
+    ///
+    /// # Panics
+    /// This function will panic if `images_per_row` or either of the image dimensions is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{Layout, Merger, KnownSizeMerger, BufferedImage, Rgb};
+    ///
+    /// // Places every image one step down and to the right of the last one.
+    /// struct DiagonalLayout;
+    ///
+    /// impl Layout for DiagonalLayout {
+    ///     fn coordinates(&self, index: u32, image_dimensions: (u32, u32), _: u32) -> (u32, u32) {
+    ///         (index * image_dimensions.0, index * image_dimensions.1)
+    ///     }
+    ///
+    ///     fn canvas_size(&self, num_images: u32, image_dimensions: (u32, u32), _: u32) -> (u32, u32) {
+    ///         (num_images * image_dimensions.0, num_images * image_dimensions.1)
+    ///     }
+    /// }
+    ///
+    /// let mut merger: KnownSizeMerger<Rgb<u8>, _, _> = KnownSizeMerger::with_layout((100, 100), 1, 3, DiagonalLayout);
+    /// merger.bulk_push(&[&BufferedImage::new(100, 100); 3]);
+    /// assert_eq!(merger.get_canvas().dimensions(), (300, 300));
+    /// ```
+    pub fn with_layout(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        layout: L,
+    ) -> Self {
+        Self::try_with_layout(image_dimensions, images_per_row, total_images, layout)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        Self {
-            canvas: ImageCell::new(canvas),
+    /// Constructs a new KnownSizeMerger like `with_layout`, but returns an error instead of panicking if it can not be laid
+    /// out.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row, which is passed on to the layout.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `layout` - The layout that decides where images are placed.
+    ///
+    /// # Returns
+    /// * `Ok` - The merger.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row` or either of the
+    ///   image dimensions is zero.
+    pub fn try_with_layout(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        layout: L,
+    ) -> Result<Self, MergeError> {
+        check_layout(image_dimensions, images_per_row)?;
+        let (width, height) = layout.canvas_size(total_images, image_dimensions, images_per_row);
+
+        Ok(Self {
+            canvas: ImageCell::new(Image::new(width, height)),
             image_dimensions,
             num_images: 0,
             images_per_row,
-            last_pasted_index: -1,
-            total_rows,
-            padding,
-        }
+            last_pasted_index: None,
+            total_rows: total_images.div_ceil(images_per_row),
+            layout,
+        })
     }
 
     /// Removes an image from the canvas at a given index. Indexing starts at 0 and works left to right, top to bottom.
     /// # Arguments
     /// * `index` - The index of the image to remove.
     pub fn remove_image(&mut self, index: u32) {
+        // The length is computed as a usize, as it can be larger than a u32 can hold.
         let container: Vec<<P as Pixel>::Subpixel> = vec![
             Zero::zero();
-            (self.image_dimensions.0 * self.image_dimensions.1 * <P as Pixel>::CHANNEL_COUNT as u32)
-                as usize
+            self.image_dimensions.0 as usize
+                * self.image_dimensions.1 as usize
+                * <P as Pixel>::CHANNEL_COUNT as usize
         ];
 
         self.remove_image_raw(index, container).unwrap(); // Can always unwrap here because we know the buffer is the right size.
-
-        let offset_x = index % self.images_per_row;
-        let offset_y = index / self.images_per_row;
-
-        let x = offset_x * self.image_dimensions.0;
-        let y = offset_y * self.image_dimensions.1;
-
-        let black_image: BufferedImage<P> =
-            Image::new(self.image_dimensions.0, self.image_dimensions.1);
-
-        paste(&self.canvas, &black_image, Point { x, y });
     }
 }
 
-impl<P, Container> Merger<P, Container> for KnownSizeMerger<P, Container>
+impl<P, Container, L> Merger<P, Container> for KnownSizeMerger<P, Container, L>
 where
     P: Pixel + Sync,
     <P as Pixel>::Subpixel: Sync,
     Container: DerefMut<Target = [P::Subpixel]> + Sync,
+    L: Layout,
 {
     fn get_canvas(&self) -> &Image<P, image::ImageBuffer<P, Container>> {
         &self.canvas
@@ -243,41 +430,23 @@ where
     }
 
     fn push(&mut self, image: &Image<P, image::ImageBuffer<P, Container>>) {
-        let (x, y) = self.get_next_paste_coordinates();
-
-        paste(&self.canvas, image, Point { x, y });
-
-        self.last_pasted_index += 1;
-        self.num_images += 1;
+        if let Err(err) = self.try_push(image) {
+            panic!("{}", err);
+        }
     }
 
     fn bulk_push(&mut self, images: &[&Image<P, image::ImageBuffer<P, Container>>]) {
-        // If we can't fit all the images we need to panic.
-        if self.additional_space() < images.len() as u32 {
-            // TODO: Maybe only take as many images as we can fit?
-            panic!("There is not enough space on the canvas to fit all the requested images.");
+        if let Err(err) = self.try_bulk_push(images) {
+            panic!("{}", err);
         }
-
-        (0..images.len()).into_par_iter().for_each(|index| {
-            let image = images[index];
-
-            // The image coordinates can easily be calculated by using the last_pasted_index
-            // and making the calculations ourselves.
-            let offset_index = (index as i32 + self.last_pasted_index + 1) as u32;
-
-            let (x, y) = self.get_paste_coordinates_unchecked(offset_index);
-            paste(&self.canvas, image, Point { x, y });
-        });
-
-        self.last_pasted_index += images.len() as i32;
-        self.num_images += images.len() as u32;
     }
 }
 
-impl<P> ResizableMerger<P> for KnownSizeMerger<P, Vec<<P as Pixel>::Subpixel>>
+impl<P, L> ResizableMerger<P> for KnownSizeMerger<P, Vec<<P as Pixel>::Subpixel>, L>
 where
     P: Pixel + Sync + Send,
     <P as Pixel>::Subpixel: Sync + Send,
+    L: Layout,
 {
     fn push_resized(&mut self, image: &BufferedImage<P>) {
         let (width, height) = self.image_dimensions;
@@ -291,7 +460,7 @@ where
             .into_par_iter()
             .map(|image| {
                 let (width, height) = self.image_dimensions;
-                return resize_nearest_neighbor(image, width, height);
+                resize_nearest_neighbor(image, width, height)
             })
             .collect();
 
@@ -1,7 +1,17 @@
 mod core;
+mod error;
+mod grid;
+mod growable;
 mod known;
+mod packing;
 mod resizable;
+mod streaming;
 
 pub use core::*;
+pub use error::*;
+pub use grid::*;
+pub use growable::*;
 pub use known::*;
+pub use packing::*;
 pub use resizable::*;
+pub use streaming::*;
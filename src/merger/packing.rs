@@ -0,0 +1,221 @@
+use super::{
+    core::{Merger, Point},
+    error::MergeError,
+};
+use crate::{cell::ImageCell, functions::paste, parallel::prelude::*, BufferedImage, Image};
+
+use image::Pixel;
+use num_traits::Zero;
+
+/// A merger that packs images of different sizes onto a canvas in rows, also known as shelf packing. Images are placed next to
+/// each other from left to right until the next image would make the row wider than the row width, and then a new row is
+/// started below it. Each row is as tall as the tallest image in it, and images are aligned to the top of their row.
+///
+/// The canvas is as wide as the row width, and grows down as rows are added. Unlike the [GrowableMerger](crate::GrowableMerger),
+/// the images pushed onto this merger do not need to be a uniform size, so the place of each image is kept track of and can be
+/// looked up with [get_placement](PackingMerger::get_placement).
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{Merger, PackingMerger, BufferedImage, Point, Rgb};
+///
+/// let mut merger: PackingMerger<Rgb<u8>> = PackingMerger::new(300);
+/// merger.push(&BufferedImage::new(200, 100));
+/// merger.push(&BufferedImage::new(100, 50));
+/// merger.push(&BufferedImage::new(150, 80));
+///
+/// // The first two images share a row, the third does not fit next to them and starts a new one.
+/// assert_eq!(merger.get_placement(1), Some((Point { x: 200, y: 0 }, (100, 50))));
+/// assert_eq!(merger.get_placement(2), Some((Point { x: 0, y: 100 }, (150, 80))));
+/// assert_eq!(merger.get_canvas().dimensions(), (300, 180));
+/// ```
+pub struct PackingMerger<P: Pixel> {
+    canvas: ImageCell<P, image::ImageBuffer<P, Vec<P::Subpixel>>>,
+    row_width: u32,  // The width of the canvas, no row of images is wider than this.
+    cursor_x: u32,   // Where the next image in the current row is placed.
+    row_y: u32,      // The top of the current row.
+    row_height: u32, // The height of the tallest image in the current row.
+    placements: Vec<(Point, (u32, u32))>, // The coordinates and dimensions of every image on the canvas, in push order.
+}
+
+impl<P> PackingMerger<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync,
+{
+    /// Constructs a new PackingMerger with an empty canvas. The canvas grows down as images are pushed onto it.
+    ///
+    /// # Arguments
+    /// * `row_width` - The width of the canvas. Images are placed next to each other until a row would grow wider than this.
+    ///
+    /// # Panics
+    /// This function will panic if `row_width` is zero.
+    pub fn new(row_width: u32) -> Self {
+        if row_width == 0 {
+            panic!("A PackingMerger must have a row width of at least one pixel.");
+        }
+
+        Self {
+            canvas: ImageCell::new(Image::new(row_width, 0)),
+            row_width,
+            cursor_x: 0,
+            row_y: 0,
+            row_height: 0,
+            placements: Vec::new(),
+        }
+    }
+
+    /// Returns the number of images that have been pasted to the canvas.
+    pub fn get_num_images(&self) -> u32 {
+        self.placements.len() as u32
+    }
+
+    /// Returns the width of the rows, which is also the width of the canvas.
+    pub fn get_row_width(&self) -> u32 {
+        self.row_width
+    }
+
+    /// Returns where the image at the given index was placed on the canvas. Indexing starts at 0 and follows the order the
+    /// images were pushed in.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image.
+    ///
+    /// # Returns
+    /// * `Some` - The coordinates of the top left corner of the image, and its (width, height).
+    /// * `None` - If there is no image at the given index.
+    pub fn get_placement(&self, index: u32) -> Option<(Point, (u32, u32))> {
+        self.placements.get(index as usize).copied()
+    }
+
+    /// Pushes an image onto the canvas, starting a new row if it does not fit next to the images in the current row, and
+    /// growing the canvas if needed.
+    ///
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. It can be any size, as long as it is not wider than the row width.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::ImageTooWide](MergeError::ImageTooWide) if the image is wider than the row width, or a
+    ///   [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would grow too tall to address. Nothing is
+    ///   pasted in either case.
+    pub fn try_push(&mut self, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        self.try_bulk_push(&[image])
+    }
+
+    /// Pushes a set of images onto the canvas in order, packing them the same way as `try_push` does. The canvas grows at most
+    /// once, and the images are pasted in parallel.
+    ///
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    ///
+    /// # Returns
+    /// * `Ok` - If every image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::ImageTooWide](MergeError::ImageTooWide) if any of the images is wider than the row width, or
+    ///   a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the canvas would grow too tall to address. Nothing is
+    ///   pasted in either case.
+    pub fn try_bulk_push(&mut self, images: &[&BufferedImage<P>]) -> Result<(), MergeError> {
+        if let Some(image) = images.iter().find(|image| image.width() > self.row_width) {
+            return Err(MergeError::ImageTooWide {
+                max_width: self.row_width,
+                got: image.width(),
+            });
+        }
+
+        // The images are laid out on a copy of the cursor, so nothing changes if the canvas would grow too large for them.
+        let too_large = || MergeError::CanvasTooLarge {
+            max_dimensions: None,
+        };
+        let (mut cursor_x, mut row_y, mut row_height) =
+            (self.cursor_x, self.row_y, self.row_height);
+        let mut placements = Vec::with_capacity(images.len());
+        for image in images {
+            let (width, height) = image.dimensions();
+            // The cursor never passes the edge of the row, so the space left in it can not underflow.
+            if cursor_x > 0 && width > self.row_width - cursor_x {
+                row_y = row_y.checked_add(row_height).ok_or_else(too_large)?;
+                cursor_x = 0;
+                row_height = 0;
+            }
+
+            placements.push((
+                Point {
+                    x: cursor_x,
+                    y: row_y,
+                },
+                (width, height),
+            ));
+            // Can not overflow, the image either starts a new row or ends before the edge of the row.
+            cursor_x += width;
+            row_height = row_height.max(height);
+        }
+
+        let height = row_y.checked_add(row_height).ok_or_else(too_large)?;
+        if height > self.canvas.height() {
+            self.grow_canvas(height)?;
+        }
+
+        self.cursor_x = cursor_x;
+        self.row_y = row_y;
+        self.row_height = row_height;
+        (0..images.len()).into_par_iter().for_each(|index| {
+            paste(&self.canvas, images[index], placements[index].0);
+        });
+        self.placements.extend(placements);
+        Ok(())
+    }
+
+    /// Grows the canvas down to the given height. Rows are stored contiguously and the width of the canvas never changes, so
+    /// the underlying buffer is resized in place.
+    ///
+    /// # Returns
+    /// * `Ok` - If the canvas was grown.
+    /// * `Err` - A [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge) if the buffer of a canvas that tall can not be
+    ///   addressed. The canvas is left untouched in this case.
+    fn grow_canvas(&mut self, height: u32) -> Result<(), MergeError> {
+        let len = (self.row_width as usize)
+            .checked_mul(height as usize)
+            .and_then(|len| len.checked_mul(<P as Pixel>::CHANNEL_COUNT as usize))
+            .ok_or(MergeError::CanvasTooLarge {
+                max_dimensions: None,
+            })?;
+
+        let canvas = std::mem::take(&mut **self.canvas.get_image_mut());
+        let mut container = canvas.into_raw();
+        container.resize(len, Zero::zero());
+
+        // The buffer is always the right size, unless the canvas dimensions were miscalculated.
+        *self.canvas.get_image_mut() = Image::try_from_raw(self.row_width, height, container)
+            .unwrap_or_else(|err| panic!("Could not grow the canvas: {}", err));
+        Ok(())
+    }
+}
+
+impl<P> Merger<P, Vec<P::Subpixel>> for PackingMerger<P>
+where
+    P: Pixel + Sync + 'static,
+    <P as Pixel>::Subpixel: Sync,
+{
+    fn get_canvas(&self) -> &BufferedImage<P> {
+        &self.canvas
+    }
+
+    fn into_canvas(self) -> BufferedImage<P> {
+        self.canvas.into_inner()
+    }
+
+    fn push(&mut self, image: &BufferedImage<P>) {
+        if let Err(err) = self.try_push(image) {
+            panic!("{}", err);
+        }
+    }
+
+    fn bulk_push(&mut self, images: &[&BufferedImage<P>]) {
+        if let Err(err) = self.try_bulk_push(images) {
+            panic!("{}", err);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use super::{core::check_layout, error::MergeError};
+use crate::BufferedImage;
+
+use image::{
+    error::{EncodingError, ImageFormatHint, UnsupportedError, UnsupportedErrorKind},
+    ExtendedColorType, ImageError, ImageFormat, Pixel, PixelWithColorType,
+};
+use num_traits::{ToPrimitive, Zero};
+use std::io::Write;
+
+/// A merger that encodes the canvas as a PNG while images are pushed onto it, so the full canvas never has to be held in
+/// memory. Only a single row of images is kept, and it is written out as soon as it is full. This makes it possible to merge
+/// images into a canvas that is far too large to fit in memory.
+///
+/// The writer must be owned, such as a [File](std::fs::File) or a [BufWriter](std::io::BufWriter) around one, because the
+/// encoder keeps hold of it until the merger is finished.
+///
+/// A PNG stores its dimensions before any of its pixels, so the total number of images has to be known up front. Any places
+/// that are left over when the merger is finished are filled with zeroes. Only 8 and 16 bit Luma, LumaA, Rgb and Rgba pixels
+/// can be encoded.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the images being pushed.
+/// * `W` - The writer the PNG is written to.
+///
+/// # Example
+/// ```
+/// use image_merger::{StreamingPngMerger, BufferedImage, Rgb};
+///
+/// let path = std::env::temp_dir().join("image_merger_streaming_example.png");
+/// let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+///
+/// let mut merger: StreamingPngMerger<Rgb<u8>, _> = StreamingPngMerger::new((100, 100), 5, 12, file).unwrap();
+/// for _ in 0..12 {
+///     merger.push(&BufferedImage::new(100, 100)).unwrap();
+/// }
+/// merger.finish().unwrap();
+///
+/// let canvas = image::open(&path).unwrap();
+/// assert_eq!((canvas.width(), canvas.height()), (500, 300));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct StreamingPngMerger<P, W>
+where
+    P: Pixel,
+    W: Write + 'static,
+{
+    stream: png::StreamWriter<'static, W>,
+    image_dimensions: (u32, u32), // The dimensions of the images being pasted (images must be a uniform size)
+    num_images: u32, // The number of images that have been pushed, including the ones already written.
+    images_per_row: u32, // The number of images per row.
+    total_images: u32, // The number of images the canvas holds.
+    row: Vec<P::Subpixel>, // The row of images that is currently being filled.
+}
+
+impl<P, W> StreamingPngMerger<P, W>
+where
+    P: Pixel + PixelWithColorType,
+    W: Write + 'static,
+{
+    /// Constructs a new StreamingPngMerger and writes the PNG header to the writer.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `writer` - The writer to write the PNG to.
+    ///
+    /// # Returns
+    /// * `Ok` - If the header was written.
+    /// * `Err` - A [MergeError::InvalidConfiguration](MergeError::InvalidConfiguration) if `images_per_row`, `total_images`
+    ///   or either of the image dimensions is zero, a [MergeError::Image](MergeError::Image) if the pixel type can not be
+    ///   encoded as a PNG, or if the header could not be written, or a [MergeError::CanvasTooLarge](MergeError::CanvasTooLarge)
+    ///   if the dimensions of the canvas or the row of images kept in memory are too large to address.
+    pub fn new(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        writer: W,
+    ) -> Result<Self, MergeError> {
+        check_layout(image_dimensions, images_per_row)?;
+        if total_images == 0 {
+            return Err(MergeError::InvalidConfiguration(
+                "there must be at least one image",
+            ));
+        }
+
+        let (color, depth) = match P::COLOR_TYPE {
+            ExtendedColorType::L8 => (png::ColorType::Grayscale, png::BitDepth::Eight),
+            ExtendedColorType::La8 => (png::ColorType::GrayscaleAlpha, png::BitDepth::Eight),
+            ExtendedColorType::Rgb8 => (png::ColorType::Rgb, png::BitDepth::Eight),
+            ExtendedColorType::Rgba8 => (png::ColorType::Rgba, png::BitDepth::Eight),
+            ExtendedColorType::L16 => (png::ColorType::Grayscale, png::BitDepth::Sixteen),
+            ExtendedColorType::La16 => (png::ColorType::GrayscaleAlpha, png::BitDepth::Sixteen),
+            ExtendedColorType::Rgb16 => (png::ColorType::Rgb, png::BitDepth::Sixteen),
+            ExtendedColorType::Rgba16 => (png::ColorType::Rgba, png::BitDepth::Sixteen),
+            color => {
+                return Err(MergeError::Image(ImageError::Unsupported(
+                    UnsupportedError::from_format_and_kind(
+                        ImageFormatHint::Exact(ImageFormat::Png),
+                        UnsupportedErrorKind::Color(color),
+                    ),
+                )))
+            }
+        };
+
+        // The dimensions are multiplied with overflow checks, so a canvas that is too large is rejected instead of wrapping
+        // around to a smaller one.
+        let too_large = || MergeError::CanvasTooLarge {
+            max_dimensions: None,
+        };
+        let total_rows = total_images.div_ceil(images_per_row);
+        let width = image_dimensions
+            .0
+            .checked_mul(images_per_row)
+            .ok_or_else(too_large)?;
+        let height = image_dimensions
+            .1
+            .checked_mul(total_rows)
+            .ok_or_else(too_large)?;
+        let row_len = (width as usize)
+            .checked_mul(image_dimensions.1 as usize)
+            .and_then(|pixels| pixels.checked_mul(<P as Pixel>::CHANNEL_COUNT as usize))
+            .filter(|&len| len <= isize::MAX as usize / std::mem::size_of::<P::Subpixel>())
+            .ok_or_else(too_large)?;
+
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(depth);
+        let stream = encoder
+            .write_header()
+            .and_then(|writer| writer.into_stream_writer())
+            .map_err(Self::encoding_error)?;
+
+        Ok(Self {
+            stream,
+            image_dimensions,
+            num_images: 0,
+            images_per_row,
+            total_images,
+            row: vec![Zero::zero(); row_len],
+        })
+    }
+
+    /// Returns the number of images that have been pushed onto the canvas.
+    pub fn get_num_images(&self) -> u32 {
+        self.num_images
+    }
+
+    /// Returns the dimensions, (x, y), of the images being pasted to the canvas.
+    pub fn get_image_dimensions(&self) -> (u32, u32) {
+        self.image_dimensions
+    }
+
+    /// Pushes an image onto the canvas. Once the current row of images is full, it is encoded and written out.
+    ///
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas. Its dimensions must match the image dimensions of the merger.
+    ///
+    /// # Returns
+    /// * `Ok` - If the image was pushed onto the canvas.
+    /// * `Err` - A [MergeError::DimensionMismatch](MergeError::DimensionMismatch) if the image is not the right size, a
+    ///   [MergeError::OutOfCapacity](MergeError::OutOfCapacity) if the canvas already holds every image, or a
+    ///   [MergeError::Image](MergeError::Image) if the row could not be written.
+    pub fn push(&mut self, image: &BufferedImage<P>) -> Result<(), MergeError> {
+        if image.dimensions() != self.image_dimensions {
+            return Err(MergeError::DimensionMismatch {
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+        if self.num_images >= self.total_images {
+            return Err(MergeError::OutOfCapacity {
+                capacity: self.total_images,
+            });
+        }
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let image_row_len = self.image_dimensions.0 as usize * channels;
+        let row_len = image_row_len * self.images_per_row as usize;
+        let start = (self.num_images % self.images_per_row) as usize * image_row_len;
+
+        let source: &[P::Subpixel] = image;
+        for (y, image_row) in source.chunks_exact(image_row_len).enumerate() {
+            let dst = y * row_len + start;
+            self.row[dst..dst + image_row_len].copy_from_slice(image_row);
+        }
+
+        self.num_images += 1;
+        if self.num_images.is_multiple_of(self.images_per_row) {
+            self.flush_row()?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the PNG, filling any places that are left over with zeroes.
+    ///
+    /// # Returns
+    /// * `Ok` - If the PNG was finished.
+    /// * `Err` - A [MergeError::Image](MergeError::Image) if the rest of the PNG could not be written.
+    pub fn finish(mut self) -> Result<(), MergeError> {
+        let total_rows = self.total_images.div_ceil(self.images_per_row);
+        let mut rows_written = self.num_images / self.images_per_row;
+
+        // The current row may be partly filled, and is written as-is. Every row after it is empty.
+        while rows_written < total_rows {
+            self.flush_row()?;
+            rows_written += 1;
+        }
+
+        self.stream.finish().map_err(Self::encoding_error)
+    }
+
+    /// Encodes the current row of images, writes it out, and clears it for the next row.
+    fn flush_row(&mut self) -> Result<(), MergeError> {
+        let bytes: Vec<u8> = match std::mem::size_of::<P::Subpixel>() {
+            1 => self.row.iter().map(|s| s.to_u8().unwrap_or(0)).collect(),
+            _ => self
+                .row
+                .iter()
+                .flat_map(|s| s.to_u16().unwrap_or(0).to_be_bytes())
+                .collect(),
+        };
+        self.stream.write_all(&bytes).map_err(ImageError::IoError)?;
+
+        self.row.fill(Zero::zero());
+        Ok(())
+    }
+
+    fn encoding_error(err: png::EncodingError) -> MergeError {
+        MergeError::Image(ImageError::Encoding(EncodingError::new(
+            ImageFormatHint::Exact(ImageFormat::Png),
+            err,
+        )))
+    }
+}
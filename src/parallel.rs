@@ -0,0 +1,67 @@
+//! The parallel iterators used throughout the crate. When the `rayon` feature is enabled these are rayon's own, and
+//! otherwise they are stand-ins with the same method names that run on the calling thread, so every loop is written once.
+
+#[cfg(feature = "rayon")]
+pub(crate) use rayon::current_num_threads;
+
+/// Returns the number of threads work is split across, which is always one without the `rayon` feature.
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn current_num_threads() -> usize {
+    1
+}
+
+pub(crate) mod prelude {
+    #[cfg(feature = "rayon")]
+    pub(crate) use rayon::prelude::*;
+
+    #[cfg(not(feature = "rayon"))]
+    pub(crate) use super::sequential::*;
+}
+
+#[cfg(not(feature = "rayon"))]
+mod sequential {
+    use std::slice::{ChunksExact, ChunksExactMut, ChunksMut};
+
+    /// A sequential stand-in for rayon's `IntoParallelIterator`.
+    pub(crate) trait IntoParallelIterator {
+        type Iter: Iterator;
+
+        fn into_par_iter(self) -> Self::Iter;
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {
+        type Iter = I::IntoIter;
+
+        fn into_par_iter(self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// A sequential stand-in for rayon's `ParallelSlice`.
+    pub(crate) trait ParallelSlice<T> {
+        fn par_chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T>;
+    }
+
+    impl<T> ParallelSlice<T> for [T] {
+        fn par_chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
+            self.chunks_exact(chunk_size)
+        }
+    }
+
+    /// A sequential stand-in for rayon's `ParallelSliceMut`.
+    pub(crate) trait ParallelSliceMut<T> {
+        fn par_chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T>;
+
+        fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> ChunksExactMut<'_, T>;
+    }
+
+    impl<T> ParallelSliceMut<T> for [T] {
+        fn par_chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
+            self.chunks_mut(chunk_size)
+        }
+
+        fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> ChunksExactMut<'_, T> {
+            self.chunks_exact_mut(chunk_size)
+        }
+    }
+}
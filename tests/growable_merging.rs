@@ -0,0 +1,3811 @@
+use image::imageops::overlay;
+use image_merger::*;
+
+static IMAGES_PER_ROW: u32 = 10;
+static TOTAL_IMAGES: u32 = 95;
+static IMAGE_WIDTH: u32 = 100;
+static IMAGE_HEIGHT: u32 = 100;
+static PADDING_X: u32 = 10;
+static PADDING_Y: u32 = 5;
+
+type RgbaImageBuffer = BufferedImage<Rgba<u8>>;
+
+fn generate_test_square() -> RgbaImageBuffer {
+    let color = |x: u32, y: u32| -> Rgba<u8> {
+        let r = x as u8;
+        let g = y as u8;
+        let b = (x + y) as u8;
+        Rgba([r, g, b, 255])
+    };
+
+    let mut image = RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    for x in 0..IMAGE_WIDTH {
+        for y in 0..IMAGE_HEIGHT {
+            image.put_pixel(x, y, color(x, y));
+        }
+    }
+
+    image
+}
+
+fn merge_images_slow(images_per_row: u32, total_images: u32) -> RgbaImageBuffer {
+    merge_images_slow_padded(images_per_row, total_images, 0, 0)
+}
+
+fn merge_images_slow_padded(
+    images_per_row: u32,
+    total_images: u32,
+    padding_x: u32,
+    padding_y: u32,
+) -> RgbaImageBuffer {
+    let total_rows = total_images.div_ceil(images_per_row);
+
+    let test_square = generate_test_square();
+
+    let mut canvas = RgbaImageBuffer::new(
+        IMAGE_WIDTH * images_per_row + (padding_x * (images_per_row - 1)),
+        IMAGE_HEIGHT * total_rows + (padding_y * (total_rows - 1)),
+    );
+
+    for index in 0..total_images {
+        let global_x = index % images_per_row;
+        let global_y = index / images_per_row;
+
+        let x = (global_x * IMAGE_WIDTH) + (global_x * padding_x);
+        let y = (global_y * IMAGE_HEIGHT) + (global_y * padding_y);
+
+        overlay(&mut *canvas, &*test_square, x as i64, y as i64)
+    }
+
+    canvas
+}
+
+#[test]
+fn test_push_merge() {
+    let test_square = generate_test_square();
+    let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES);
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    for _ in 0..TOTAL_IMAGES {
+        merger.push(&test_square);
+    }
+
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES);
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+#[test]
+fn test_bulk_push_merge() {
+    let test_square = generate_test_square();
+    let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES);
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES);
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+#[test]
+fn test_bulk_push_after_push() {
+    // Pushing a few images one at a time and then bulk pushing the rest should be the same as pushing them all one at a time.
+    let test_square = generate_test_square();
+    let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES);
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    for _ in 0..3 {
+        merger.push(&test_square);
+    }
+    merger.bulk_push(&vec![&test_square; (TOTAL_IMAGES - 3) as usize]);
+
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES);
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+fn generate_colored_square(index: u8) -> RgbaImageBuffer {
+    RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([index, 0, 0, 255]))
+}
+
+#[test]
+fn test_remove_image_shifts_following_images() {
+    let squares: Vec<RgbaImageBuffer> = (0..5).map(generate_colored_square).collect();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.bulk_push(&squares.iter().collect::<Vec<_>>());
+    assert!(merger.remove_image(1).is_some());
+
+    let mut expected: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    expected.bulk_push(&[&squares[0], &squares[2], &squares[3], &squares[4]]);
+    expected.push(&RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT));
+
+    assert_eq!(merger.get_num_images(), 4);
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+}
+
+#[test]
+fn test_remove_image_out_of_range() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.push(&test_square);
+
+    assert!(merger.remove_image(1).is_none());
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[test]
+fn test_remove_image_empties_last_row() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&vec![&test_square; IMAGES_PER_ROW as usize + 1]);
+    merger.remove_image(0).unwrap();
+
+    // The remaining images fit in the first row, and the now empty second row is reused by the next push.
+    assert_eq!(merger.get_num_images(), IMAGES_PER_ROW);
+    assert_eq!(merger.get_canvas().height(), IMAGE_HEIGHT * 2);
+
+    merger.push(&test_square);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, IMAGES_PER_ROW + 1)
+    );
+}
+
+#[test]
+fn test_new_has_a_single_empty_row() {
+    let merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    assert_eq!(merger.get_num_images(), 0);
+    assert_eq!(merger.get_canvas().width(), IMAGE_WIDTH * IMAGES_PER_ROW);
+    assert_eq!(merger.get_canvas().height(), IMAGE_HEIGHT);
+}
+
+#[test]
+fn test_last_pasted_index() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    assert_eq!(merger.last_pasted_index(), None);
+
+    merger.push(&generate_colored_square(0));
+    assert_eq!(merger.last_pasted_index(), Some(0));
+    merger.push(&generate_colored_square(1));
+    assert_eq!(merger.last_pasted_index(), Some(1));
+    assert_eq!(merger.get_image_at(0), Some(generate_colored_square(0)));
+    assert_eq!(merger.get_image_at(1), Some(generate_colored_square(1)));
+
+    merger.pop();
+    merger.pop();
+    assert_eq!(merger.last_pasted_index(), None);
+}
+
+#[test]
+#[should_panic(expected = "at least one image per row")]
+fn test_new_zero_images_per_row() {
+    let _: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 0);
+}
+
+#[test]
+#[should_panic(expected = "width or height of zero")]
+fn test_new_zero_image_dimensions() {
+    let _: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, 0), IMAGES_PER_ROW);
+}
+
+#[test]
+fn test_paste_coordinates_scale_with_image_size() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    for index in 0..4 {
+        merger.push(&generate_colored_square(index + 1));
+    }
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(0, 0), &Rgba([1, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(IMAGE_WIDTH, 0), &Rgba([2, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(IMAGE_WIDTH * 2, 0), &Rgba([3, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(0, IMAGE_HEIGHT), &Rgba([4, 0, 0, 255]));
+    assert_eq!(
+        canvas.get_pixel(IMAGE_WIDTH - 1, IMAGE_HEIGHT - 1),
+        &Rgba([1, 0, 0, 255])
+    );
+}
+
+#[test]
+fn test_canvas_grows_one_row_at_a_time() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    for expected_rows in 1..=4 {
+        merger.push(&test_square);
+        merger.push(&test_square);
+        assert_eq!(merger.get_canvas().height(), IMAGE_HEIGHT * expected_rows);
+    }
+}
+
+#[test]
+fn test_push_past_full_row() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    for _ in 0..IMAGES_PER_ROW + 1 {
+        merger.push(&test_square);
+    }
+
+    assert_eq!(merger.get_num_images(), IMAGES_PER_ROW + 1);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, IMAGES_PER_ROW + 1)
+    );
+}
+
+#[test]
+fn test_save() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+
+    let path = std::env::temp_dir().join("image_merger_test_save.png");
+    merger.save(&path).unwrap();
+
+    let saved = image::open(&path).unwrap().into_rgba8();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&saved, &**merger.get_canvas());
+}
+
+#[test]
+fn test_save_unsupported_format() {
+    let merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    let path = std::env::temp_dir().join("image_merger_test_save.unknown");
+    assert!(merger.save(path).is_err());
+}
+
+#[test]
+fn test_encode() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&[&test_square; 15]);
+
+    let bytes = merger.encode(image::ImageFormat::Png).unwrap();
+    let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png)
+        .unwrap()
+        .into_rgba8();
+    assert_eq!(&decoded, &**merger.get_canvas());
+
+    // JPEG can not hold an alpha channel.
+    assert!(merger.encode(image::ImageFormat::Jpeg).is_err());
+}
+
+#[test]
+fn test_canvas_access() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.push(&test_square);
+
+    let canvas: &ImageBuffer<Rgba<u8>, Vec<u8>> = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(1, 2), test_square.get_pixel(1, 2));
+
+    let buffer: ImageBuffer<Rgba<u8>, Vec<u8>> = merger.into_canvas().into_buffer();
+    assert_eq!(
+        buffer.dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT)
+    );
+}
+
+#[test]
+fn test_with_capacity_fewer_images() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES);
+    assert_eq!(
+        merger.get_canvas().height(),
+        IMAGE_HEIGHT * TOTAL_IMAGES.div_ceil(IMAGES_PER_ROW)
+    );
+
+    // Pushing fewer images than expected leaves the trailing space empty.
+    for _ in 0..TOTAL_IMAGES - 20 {
+        merger.push(&test_square);
+    }
+    let mut slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES - 20);
+    let mut expected =
+        RgbaImageBuffer::new(merger.get_canvas().width(), merger.get_canvas().height());
+    image::imageops::replace(&mut *expected, &*slow_merge, 0, 0);
+    assert_eq!(merger.get_canvas(), &expected);
+
+    // Pushing more images than expected grows the canvas as normal.
+    for _ in 0..30 {
+        merger.push(&test_square);
+    }
+    slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES + 10);
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+#[test]
+fn test_growth_strategies() {
+    let square = generate_test_square();
+
+    // Returns the number of times the canvas grew while pushing the given number of images one at a time.
+    let count_growths = |strategy: GrowthStrategy, total: u32| {
+        let mut merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2).with_growth_strategy(strategy);
+        let allocated_rows =
+            |merger: &GrowableMerger<Rgba<u8>>| merger.get_canvas().height() / IMAGE_HEIGHT;
+        let mut growths = 0;
+        for _ in 0..total {
+            let rows = allocated_rows(&merger);
+            merger.push(&square);
+            if allocated_rows(&merger) != rows {
+                growths += 1;
+            }
+        }
+        (growths, allocated_rows(&merger))
+    };
+
+    assert_eq!(count_growths(GrowthStrategy::SingleRow, 64), (31, 32));
+    assert_eq!(count_growths(GrowthStrategy::FixedRows(4), 64), (8, 33));
+    assert_eq!(count_growths(GrowthStrategy::FixedRows(0), 8), (3, 4));
+
+    // Doubling the rows means the canvas grows logarithmically many times.
+    assert_eq!(count_growths(GrowthStrategy::Double, 64), (5, 32));
+    assert_eq!(count_growths(GrowthStrategy::Double, 65), (6, 64));
+}
+
+#[test]
+fn test_double_growth_respects_max_canvas_dimensions() {
+    let square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 1)
+        .with_growth_strategy(GrowthStrategy::Double)
+        .with_max_canvas_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT * 5));
+    for _ in 0..5 {
+        merger.push(&square);
+    }
+
+    // Doubling from four rows would pass the limit, so the canvas only grew by the row it needed.
+    assert_eq!(merger.get_canvas().height(), IMAGE_HEIGHT * 5);
+    assert!(matches!(
+        merger.try_push(&square),
+        Err(MergeError::CanvasTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_growth_preserves_existing_rows() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&vec![&test_square; IMAGES_PER_ROW as usize]);
+    let before = merger.get_canvas().as_raw().clone();
+
+    merger.push(&test_square);
+    let after = merger.get_canvas().as_raw();
+
+    assert_eq!(after.len(), before.len() * 2);
+    assert_eq!(&after[..before.len()], &before[..]);
+}
+
+#[test]
+fn test_try_push_dimension_mismatch() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    let result = merger.try_push(&RgbaImageBuffer::new(IMAGE_WIDTH * 2, IMAGE_HEIGHT));
+    assert!(matches!(
+        result,
+        Err(MergeError::DimensionMismatch {
+            expected: (100, 100),
+            got: (200, 100),
+        })
+    ));
+    assert_eq!(merger.get_num_images(), 0);
+
+    assert!(merger.try_push(&generate_test_square()).is_ok());
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Expected an image of size 100x100")]
+fn test_push_dimension_mismatch() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.push(&RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT + 1));
+}
+
+#[test]
+fn test_push_merge_padding() {
+    let test_square = generate_test_square();
+    let slow_merge = merge_images_slow_padded(IMAGES_PER_ROW, TOTAL_IMAGES, PADDING_X, PADDING_Y);
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW).with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+
+    for _ in 0..TOTAL_IMAGES {
+        merger.push(&test_square);
+    }
+
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+#[test]
+fn test_bulk_push_merge_padding() {
+    let test_square = generate_test_square();
+    let slow_merge = merge_images_slow_padded(IMAGES_PER_ROW, TOTAL_IMAGES, PADDING_X, PADDING_Y);
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            });
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+#[test]
+fn test_remove_image_padding() {
+    let test_square = generate_test_square();
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW).with_padding(padding);
+    merger.bulk_push(&vec![&test_square; IMAGES_PER_ROW as usize + 1]);
+    merger.remove_image(3).unwrap();
+    merger.push(&test_square);
+
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow_padded(IMAGES_PER_ROW, IMAGES_PER_ROW + 1, PADDING_X, PADDING_Y)
+    );
+}
+
+#[test]
+fn test_background() {
+    let background = Rgba([200, 200, 200, 255]);
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        })
+        .with_background(background);
+    merger.push(&test_square);
+    merger.push(&test_square);
+
+    // The empty third place and the padding are filled with the background.
+    let canvas = merger.get_canvas();
+    assert_eq!(
+        canvas.get_pixel((IMAGE_WIDTH + PADDING_X) * 2, 0),
+        &background
+    );
+    assert_eq!(
+        canvas.get_pixel(canvas.width() - 1, IMAGE_HEIGHT - 1),
+        &background
+    );
+    assert_eq!(canvas.get_pixel(IMAGE_WIDTH, 0), &background);
+
+    // Rows added by growing the canvas are filled with the background too.
+    merger.push(&test_square);
+    merger.push(&test_square);
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(0, IMAGE_HEIGHT), &background);
+    assert_eq!(
+        canvas.get_pixel(canvas.width() - 1, canvas.height() - 1),
+        &background
+    );
+    assert_eq!(
+        canvas.get_pixel(0, IMAGE_HEIGHT + PADDING_Y),
+        test_square.get_pixel(0, 0)
+    );
+
+    // Removed images are cleared to the background.
+    merger.remove_image(3).unwrap();
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(0, IMAGE_HEIGHT + PADDING_Y), &background);
+}
+
+#[test]
+fn test_checker_background() {
+    let (a, b) = (Rgba([204, 204, 204, 255]), Rgba([153, 153, 153, 255]));
+    let size = 16;
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        })
+        .with_background_pattern(Background::Checker { a, b, size });
+    merger.push(&test_square);
+
+    // Every pixel of the canvas without an image is part of a checkerboard aligned to the top left corner.
+    let check = |canvas: &BufferedImage<Rgba<u8>>, from_y: u32| {
+        for y in from_y..canvas.height() {
+            for x in IMAGE_WIDTH..canvas.width() {
+                let expected = if (x / size) % 2 == (y / size) % 2 {
+                    a
+                } else {
+                    b
+                };
+                assert_eq!(canvas.get_pixel(x, y), &expected);
+            }
+        }
+    };
+    check(merger.get_canvas(), 0);
+    assert_eq!(
+        merger.get_canvas().get_pixel(0, 0),
+        test_square.get_pixel(0, 0)
+    );
+
+    // Rows added by growing the canvas continue the pattern.
+    for _ in 0..3 {
+        merger.push(&test_square);
+    }
+    assert_eq!(merger.get_num_rows(), 2);
+    check(merger.get_canvas(), IMAGE_HEIGHT);
+}
+
+#[test]
+#[should_panic]
+fn test_checker_background_zero_size() {
+    let _: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_background_pattern(Background::Checker {
+            a: Rgba([0, 0, 0, 255]),
+            b: Rgba([255, 255, 255, 255]),
+            size: 0,
+        });
+}
+
+#[test]
+fn test_edge_paste_fits_exactly_or_grows() {
+    let squares: Vec<RgbaImageBuffer> = (0..4).map(generate_colored_square).collect();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    for square in &squares[..3] {
+        merger.push(square);
+    }
+
+    // The last image of the row ends exactly at the right edge of the canvas.
+    let (x, y) = merger.coordinates_of_index(2);
+    assert_eq!(x + IMAGE_WIDTH, merger.get_canvas().width());
+    assert_eq!(y + IMAGE_HEIGHT, merger.get_canvas().height());
+    assert_eq!(
+        merger
+            .get_canvas()
+            .get_pixel(x + IMAGE_WIDTH - 1, y + IMAGE_HEIGHT - 1),
+        squares[2].get_pixel(IMAGE_WIDTH - 1, IMAGE_HEIGHT - 1)
+    );
+
+    // The next image would land below the canvas, so the canvas grows instead of clipping it.
+    assert!(merger.will_grow_on_next_push());
+    merger.push(&squares[3]);
+    let (x, y) = merger.coordinates_of_index(3);
+    assert_eq!(x, 0);
+    assert_eq!(y + IMAGE_HEIGHT, merger.get_canvas().height());
+    assert_eq!(
+        merger.get_image_at(3).unwrap().as_raw(),
+        squares[3].as_raw()
+    );
+}
+
+#[test]
+fn test_get_image_at() {
+    let squares: Vec<RgbaImageBuffer> = (0..7).map(generate_colored_square).collect();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    merger.bulk_push(&squares.iter().collect::<Vec<_>>());
+
+    for (index, square) in squares.iter().enumerate() {
+        assert_eq!(merger.get_image_at(index as u32).as_ref(), Some(square));
+    }
+    assert!(merger.get_image_at(7).is_none());
+}
+
+#[test]
+fn test_replace_image() {
+    let squares: Vec<RgbaImageBuffer> = (0..4).map(generate_colored_square).collect();
+    let replacement = RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    merger.bulk_push(&squares.iter().collect::<Vec<_>>());
+    merger.replace_image(1, &replacement).unwrap();
+
+    assert_eq!(merger.get_num_images(), 4);
+    assert_eq!(merger.get_image_at(0).as_ref(), Some(&squares[0]));
+    assert_eq!(merger.get_image_at(1).as_ref(), Some(&replacement));
+    assert_eq!(merger.get_image_at(2).as_ref(), Some(&squares[2]));
+    assert_eq!(merger.get_image_at(3).as_ref(), Some(&squares[3]));
+
+    assert!(matches!(
+        merger.replace_image(4, &replacement),
+        Err(MergeError::IndexOutOfBounds {
+            index: 4,
+            num_images: 4
+        })
+    ));
+}
+
+#[test]
+fn test_grid_accessors() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 12);
+    assert_eq!(merger.get_num_rows(), 0);
+    assert_eq!(merger.get_num_columns(), 3);
+
+    merger.bulk_push(&[&test_square; 4]);
+    assert_eq!(merger.get_num_rows(), 2);
+    assert_eq!(
+        merger.get_canvas_dimensions(),
+        (IMAGE_WIDTH * 3, IMAGE_HEIGHT * 4)
+    );
+}
+
+#[test]
+fn test_index_coordinates_round_trip() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    merger.bulk_push(&[&test_square; 5]);
+
+    assert_eq!(merger.coordinates_of_index(0), (0, 0));
+    assert_eq!(
+        merger.coordinates_of_index(4),
+        (IMAGE_WIDTH + PADDING_X, IMAGE_HEIGHT + PADDING_Y)
+    );
+
+    for index in 0..5 {
+        let (x, y) = merger.coordinates_of_index(index);
+        assert_eq!(merger.index_at_pixel(x, y), Some(index));
+        assert_eq!(
+            merger.index_at_pixel(x + IMAGE_WIDTH - 1, y + IMAGE_HEIGHT - 1),
+            Some(index)
+        );
+    }
+
+    // Pixels in the padding, in an empty place or off of the canvas do not belong to any image.
+    assert_eq!(merger.index_at_pixel(IMAGE_WIDTH, 0), None);
+    assert_eq!(merger.index_at_pixel(0, IMAGE_HEIGHT), None);
+    assert_eq!(
+        merger.coordinates_of_index(5),
+        (2 * (IMAGE_WIDTH + PADDING_X), IMAGE_HEIGHT + PADDING_Y)
+    );
+    assert_eq!(
+        merger.index_at_pixel(2 * (IMAGE_WIDTH + PADDING_X), IMAGE_HEIGHT + PADDING_Y),
+        None
+    );
+    assert_eq!(merger.index_at_pixel(10_000, 0), None);
+}
+
+#[test]
+fn test_push_does_not_blend() {
+    let transparent =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([10, 20, 30, 0]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 1)
+        .with_background(Rgba([255, 255, 255, 255]));
+    merger.push(&transparent);
+
+    assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgba([10, 20, 30, 0]));
+}
+
+#[test]
+fn test_bulk_push_matches_sequential_push() {
+    let squares: Vec<RgbaImageBuffer> = (0..=255).map(generate_colored_square).collect();
+
+    let mut sequential: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 7);
+    for square in &squares {
+        sequential.push(square);
+    }
+
+    let mut parallel: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 7);
+    parallel.bulk_push(&squares.iter().collect::<Vec<_>>());
+
+    assert_eq!(
+        parallel.get_canvas().as_raw(),
+        sequential.get_canvas().as_raw()
+    );
+}
+
+#[test]
+fn test_bulk_push_bands_match_sequential_push() {
+    // Padding, borders and a header move the row bands the bulk push pastes into away from the multiples of the image height.
+    let squares: Vec<RgbaImageBuffer> = (0..=40).map(generate_colored_square).collect();
+    let merger = || {
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4)
+            .with_padding(Padding { x: 3, y: 7 })
+            .with_borders(2, Rgba([0, 0, 255, 255]))
+            .with_header(RgbaImageBuffer::new_from_pixel(
+                10,
+                5,
+                Rgba([255, 0, 0, 255]),
+            ))
+            .with_footer(RgbaImageBuffer::new_from_pixel(
+                10,
+                9,
+                Rgba([0, 255, 0, 255]),
+            ))
+            .with_fill_directions(
+                HorizontalDirection::RightToLeft,
+                VerticalDirection::BottomToTop,
+            )
+    };
+
+    let mut sequential: GrowableMerger<Rgba<u8>> = merger();
+    for square in &squares {
+        sequential.push(square);
+    }
+
+    let mut parallel: GrowableMerger<Rgba<u8>> = merger();
+    parallel.bulk_push(&squares.iter().collect::<Vec<_>>());
+
+    assert_eq!(
+        parallel.get_canvas().as_raw(),
+        sequential.get_canvas().as_raw()
+    );
+}
+
+#[test]
+fn test_clear() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+    merger.clear();
+
+    assert_eq!(merger.get_num_images(), 0);
+    assert!(merger
+        .get_canvas()
+        .as_raw()
+        .iter()
+        .all(|subpixel| *subpixel == 0));
+
+    // The canvas keeps its size, and is reused by the next batch.
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES)
+    );
+}
+
+#[test]
+fn test_reset_to() {
+    let test_square = generate_test_square();
+    let background = Rgba([1, 2, 3, 4]);
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_background(background);
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+
+    merger.reset_to(15).unwrap();
+    assert_eq!(merger.get_num_images(), 0);
+    assert_eq!(merger.get_canvas().height(), IMAGE_HEIGHT * 2);
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .all(|pixel| *pixel == background));
+
+    merger.reset_to(35).unwrap();
+    assert_eq!(merger.get_canvas().height(), IMAGE_HEIGHT * 4);
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .all(|pixel| *pixel == background));
+}
+
+#[test]
+fn test_pop() {
+    let test_square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&vec![&test_square; IMAGES_PER_ROW as usize + 2]);
+
+    assert!(merger.pop());
+    assert_eq!(merger.get_num_images(), IMAGES_PER_ROW + 1);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, IMAGES_PER_ROW + 1)
+    );
+
+    // The popped place is reused by the next push.
+    merger.push(&test_square);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, IMAGES_PER_ROW + 2)
+    );
+
+    merger.clear();
+    assert!(!merger.pop());
+}
+
+#[test]
+fn test_push_path() {
+    let test_square = generate_test_square();
+    let path = std::env::temp_dir().join("image_merger_test_push_path.png");
+    test_square.save(&path).unwrap();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.push_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(merger.get_num_images(), 1);
+    assert_eq!(merger.get_image_at(0).as_ref(), Some(&test_square));
+
+    let missing = std::env::temp_dir().join("image_merger_test_push_path_missing.png");
+    assert!(matches!(
+        merger.push_path(missing),
+        Err(MergeError::Image(_))
+    ));
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[test]
+fn test_push_dir() {
+    let dir = std::env::temp_dir().join("image_merger_test_push_dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir(&dir).unwrap();
+
+    // Saved out of order to make sure the images are pushed sorted by file name.
+    for index in [2, 0, 1] {
+        generate_colored_square(index)
+            .save(dir.join(format!("{}.png", index)))
+            .unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), "not an image").unwrap();
+    std::fs::write(dir.join("3.png"), "not a png").unwrap();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    assert!(matches!(
+        merger.push_dir(&dir, false),
+        Err(MergeError::Image(_))
+    ));
+    assert_eq!(merger.get_num_images(), 3);
+
+    merger.clear();
+    assert_eq!(merger.push_dir(&dir, true).unwrap(), 3);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    for index in 0..3 {
+        assert_eq!(
+            merger.get_image_at(index as u32),
+            Some(generate_colored_square(index))
+        );
+    }
+}
+
+#[test]
+fn test_export_tiles() {
+    let dir = std::env::temp_dir().join("image_merger_test_export_tiles");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir(&dir).unwrap();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    for index in 0..5 {
+        merger.push(&generate_colored_square(index));
+    }
+    merger
+        .export_tiles(&dir, |index| format!("tile_{:02}.png", index))
+        .unwrap();
+
+    // Only the five images are saved, not the empty place at the end of the second row.
+    let mut files: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            "tile_00.png",
+            "tile_01.png",
+            "tile_02.png",
+            "tile_03.png",
+            "tile_04.png"
+        ]
+    );
+
+    // Pushing the tiles back in reproduces the original canvas.
+    let mut restored: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3).with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    assert_eq!(restored.push_dir(&dir, false).unwrap(), 5);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(restored.get_canvas().as_raw(), merger.get_canvas().as_raw());
+}
+
+#[test]
+fn test_crop_region() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    for index in 0..15 {
+        merger.push(&generate_colored_square(index));
+    }
+
+    let region = merger.crop_region(150, 50, 100, 100).unwrap();
+    assert_eq!(region.dimensions(), (100, 100));
+    assert_eq!(region.get_pixel(0, 0), &Rgba([1, 0, 0, 255]));
+    assert_eq!(region.get_pixel(50, 0), &Rgba([2, 0, 0, 255]));
+    assert_eq!(region.get_pixel(0, 50), &Rgba([11, 0, 0, 255]));
+
+    // Regions are clamped to the canvas, and must start on it.
+    let (width, height) = merger.get_canvas().dimensions();
+    let clamped = merger
+        .crop_region(width - 10, height - 20, 100, 100)
+        .unwrap();
+    assert_eq!(clamped.dimensions(), (10, 20));
+    assert!(merger.crop_region(width, 0, 10, 10).is_err());
+    assert!(merger.crop_region(0, 0, 0, 10).is_err());
+}
+
+#[test]
+fn test_crop_tiles() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    for index in 0..12 {
+        merger.push(&generate_colored_square(index));
+    }
+
+    // A 2x2 block of tiles, including the padding between them.
+    let block = merger.crop_tiles(1..3, 1..3).unwrap();
+    assert_eq!(
+        block.dimensions(),
+        (IMAGE_WIDTH * 2 + PADDING_X, IMAGE_HEIGHT * 2 + PADDING_Y)
+    );
+    assert_eq!(block.get_pixel(0, 0), &Rgba([5, 0, 0, 255]));
+    assert_eq!(block.get_pixel(IMAGE_WIDTH, 0), &Rgba([0, 0, 0, 0]));
+    assert_eq!(
+        block.get_pixel(IMAGE_WIDTH + PADDING_X, IMAGE_HEIGHT + PADDING_Y),
+        &Rgba([10, 0, 0, 255])
+    );
+
+    // A single tile matches the image at its index.
+    let tile = merger.crop_tiles(2..3, 3..4).unwrap();
+    assert_eq!(tile.as_raw(), merger.get_image_at(11).unwrap().as_raw());
+
+    // Ranges are clamped to the grid, and must not be empty.
+    assert_eq!(
+        merger.crop_tiles(0..10, 3..10).unwrap().dimensions(),
+        (IMAGE_WIDTH, merger.get_canvas().height())
+    );
+    assert!(merger.crop_tiles(3..4, 0..1).is_err());
+    assert!(merger.crop_tiles(0..0, 0..1).is_err());
+}
+
+#[test]
+fn test_encode_as_gif() {
+    use image::AnimationDecoder;
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    let colors = [
+        Rgba([255, 0, 0, 255]),
+        Rgba([0, 255, 0, 255]),
+        Rgba([0, 0, 255, 255]),
+    ];
+    for color in colors {
+        merger.push(&RgbaImageBuffer::new_from_pixel(
+            IMAGE_WIDTH,
+            IMAGE_HEIGHT,
+            color,
+        ));
+    }
+
+    let mut gif = Vec::new();
+    merger
+        .encode_as_gif(&mut gif, std::time::Duration::from_millis(250))
+        .unwrap();
+
+    // Every image becomes a frame of its own size, in push order, and the empty place is skipped.
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(gif)).unwrap();
+    let frames = decoder.into_frames().collect_frames().unwrap();
+    assert_eq!(frames.len(), 3);
+    for (frame, color) in frames.iter().zip(colors) {
+        assert_eq!(frame.buffer().dimensions(), (IMAGE_WIDTH, IMAGE_HEIGHT));
+        assert_eq!(frame.buffer().get_pixel(50, 50), &color);
+        assert_eq!(frame.delay().numer_denom_ms(), (250, 1));
+    }
+}
+
+#[test]
+fn test_resize_on_push() {
+    let large_square =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH * 2, IMAGE_HEIGHT * 3, Rgba([9, 0, 0, 255]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_resize_on_push(image::imageops::FilterType::Nearest, false);
+    merger.push(&large_square);
+    merger.bulk_push(&[&large_square, &generate_test_square()]);
+
+    assert_eq!(merger.get_num_images(), 3);
+    assert_eq!(merger.get_image_at(0), Some(generate_colored_square(9)));
+    assert_eq!(merger.get_image_at(1), Some(generate_colored_square(9)));
+    assert_eq!(merger.get_image_at(2), Some(generate_test_square()));
+}
+
+#[test]
+fn test_resize_on_push_preserve_aspect_ratio() {
+    let background = Rgba([0, 0, 255, 255]);
+    let wide_image =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH * 2, IMAGE_HEIGHT, Rgba([9, 0, 0, 255]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_background(background)
+        .with_resize_on_push(image::imageops::FilterType::Nearest, true);
+    merger.push(&wide_image);
+
+    // The image is scaled to 100x50 and centered, leaving 25 pixels of background above and below it.
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(50, 24), &background);
+    assert_eq!(canvas.get_pixel(50, 25), &Rgba([9, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(50, 74), &Rgba([9, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(50, 75), &background);
+}
+
+#[test]
+fn test_cell_alignment() {
+    let background = Rgba([0, 0, 255, 255]);
+    let color = Rgba([9, 0, 0, 255]);
+    let small_image = RgbaImageBuffer::new_from_pixel(40, 20, color);
+
+    let expected = [
+        (CellAlignment::TopLeft, (0, 0)),
+        (CellAlignment::Center, (30, 40)),
+        (CellAlignment::Right, (60, 40)),
+        (CellAlignment::BottomRight, (60, 80)),
+    ];
+    for (alignment, (x, y)) in expected {
+        let mut merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+                .with_background(background)
+                .with_cell_alignment(alignment);
+        merger.push(&generate_test_square());
+        merger.bulk_push(&[&small_image]);
+
+        // The second place holds the small image at the aligned offset, with the background around it.
+        let image = merger.get_image_at(1).unwrap();
+        assert_eq!(image.get_pixel(x, y), &color);
+        assert_eq!(image.get_pixel(x + 39, y + 19), &color);
+        if x > 0 {
+            assert_eq!(image.get_pixel(x - 1, y), &background);
+        }
+        if y + 20 < IMAGE_HEIGHT {
+            assert_eq!(image.get_pixel(x, y + 20), &background);
+        }
+    }
+
+    // Without an alignment, undersized images are still rejected.
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    assert!(matches!(
+        merger.try_push(&small_image),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+
+    // Oversized images are rejected even with an alignment.
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_cell_alignment(CellAlignment::Center);
+    let large_image = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH + 1, 20, color);
+    assert!(matches!(
+        merger.try_push(&large_image),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_cell_alignment_of_letterboxed_images() {
+    let background = Rgba([0, 0, 255, 255]);
+    let wide_image =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH * 2, IMAGE_HEIGHT, Rgba([9, 0, 0, 255]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_background(background)
+        .with_resize_on_push(image::imageops::FilterType::Nearest, true)
+        .with_cell_alignment(CellAlignment::Top);
+    merger.push(&wide_image);
+
+    // The image is scaled to 100x50 and placed at the top of its place.
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(50, 0), &Rgba([9, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(50, 49), &Rgba([9, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(50, 50), &background);
+}
+
+#[test]
+fn test_column_major_fill_order() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        })
+        .with_fill_order(FillOrder::ColumnMajor);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH, IMAGE_HEIGHT * 3 + PADDING_Y * 2)
+    );
+
+    for index in 0..4 {
+        merger.push(&generate_colored_square(index));
+    }
+    merger.bulk_push(&[
+        &generate_colored_square(4),
+        &generate_colored_square(5),
+        &generate_colored_square(6),
+    ]);
+
+    // Seven images in columns of three need three columns.
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (
+            IMAGE_WIDTH * 3 + PADDING_X * 2,
+            IMAGE_HEIGHT * 3 + PADDING_Y * 2
+        )
+    );
+    assert_eq!(merger.get_num_columns(), 3);
+    assert_eq!(merger.get_num_rows(), 3);
+    assert_eq!(
+        merger.coordinates_of_index(1),
+        (0, IMAGE_HEIGHT + PADDING_Y)
+    );
+    assert_eq!(merger.coordinates_of_index(3), (IMAGE_WIDTH + PADDING_X, 0));
+
+    // Growing the canvas sideways must keep every image that was already pasted in place.
+    for index in 0..7 {
+        assert_eq!(
+            merger.get_image_at(index),
+            Some(generate_colored_square(index as u8))
+        );
+        let (x, y) = merger.coordinates_of_index(index);
+        assert_eq!(merger.index_at_pixel(x, y), Some(index));
+    }
+    assert_eq!(
+        merger.index_at_pixel(IMAGE_WIDTH * 2 + PADDING_X * 2, IMAGE_HEIGHT + PADDING_Y),
+        None
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_fill_order_after_push_panics() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    merger.push(&generate_test_square());
+    let _ = merger.with_fill_order(FillOrder::ColumnMajor);
+}
+
+#[test]
+fn test_fixed_grid() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 4, 3);
+    let canvas_ptr = merger.get_canvas().as_raw().as_ptr();
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * 4, IMAGE_HEIGHT * 3)
+    );
+
+    let square = generate_test_square();
+    let squares: Vec<&RgbaImageBuffer> = (0..11).map(|_| &square).collect();
+    merger.bulk_push(&squares);
+    merger.push(&square);
+
+    // The grid is full, so both kinds of push must fail without touching the canvas.
+    assert!(matches!(
+        merger.try_push(&square),
+        Err(MergeError::OutOfCapacity { capacity: 12 })
+    ));
+    assert!(matches!(
+        merger.try_bulk_push(&[&square]),
+        Err(MergeError::OutOfCapacity { capacity: 12 })
+    ));
+    assert_eq!(merger.get_num_images(), 12);
+
+    // The grid can not make room for more images than it holds, but it is still cleared.
+    assert!(matches!(
+        merger.reset_to(100),
+        Err(MergeError::OutOfCapacity { capacity: 12 })
+    ));
+    assert_eq!(merger.get_num_images(), 0);
+    merger.reset_to(12).unwrap();
+    merger.bulk_push(&squares);
+    assert!(matches!(
+        merger.try_bulk_push(&[&square, &square]),
+        Err(MergeError::OutOfCapacity { capacity: 12 })
+    ));
+    assert_eq!(merger.get_num_images(), 11);
+
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * 4, IMAGE_HEIGHT * 3)
+    );
+    assert_eq!(merger.get_canvas().as_raw().as_ptr(), canvas_ptr);
+}
+
+#[test]
+fn test_fixed_grid_column_major() {
+    let merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 4, 3)
+            .with_fill_order(FillOrder::ColumnMajor);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * 4, IMAGE_HEIGHT * 3)
+    );
+    assert_eq!(merger.coordinates_of_index(1), (0, IMAGE_HEIGHT));
+}
+
+#[test]
+#[should_panic]
+fn test_fixed_grid_push_panics_when_full() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 1, 1);
+    merger.push(&generate_test_square());
+    merger.push(&generate_test_square());
+}
+
+#[test]
+fn test_try_fixed_grid() {
+    // 65536 by 65536 places is one more image than a u32 can count.
+    assert!(matches!(
+        GrowableMerger::<Rgba<u8>>::try_fixed_grid((1, 1), 65536, 65536),
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: None
+        })
+    ));
+    assert!(matches!(
+        GrowableMerger::<Rgba<u8>>::try_fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let merger =
+        GrowableMerger::<Rgba<u8>>::try_fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 4, 3).unwrap();
+    assert_eq!(merger.capacity(), 12);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * 4, IMAGE_HEIGHT * 3)
+    );
+}
+
+#[test]
+#[should_panic(expected = "too large")]
+fn test_fixed_grid_overflowing_capacity() {
+    let _ = GrowableMerger::<Rgba<u8>>::fixed_grid((1, 1), 65536, 65536);
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES);
+    for _ in 0..25 {
+        merger.push(&generate_test_square());
+    }
+    merger.shrink_to_fit().unwrap();
+
+    let canvas = merger.get_canvas();
+    assert_eq!(
+        canvas.dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT * 3)
+    );
+    assert_eq!(canvas.as_raw().capacity(), canvas.as_raw().len());
+    assert_eq!(
+        canvas.as_raw(),
+        merge_images_slow(IMAGES_PER_ROW, 25).as_raw()
+    );
+
+    // Already tightly sized, so nothing changes.
+    let canvas_ptr = canvas.as_raw().as_ptr();
+    merger.shrink_to_fit().unwrap();
+    assert_eq!(merger.get_canvas().as_raw().as_ptr(), canvas_ptr);
+
+    // An empty merger keeps a single row.
+    merger.clear();
+    merger.shrink_to_fit().unwrap();
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT)
+    );
+}
+
+#[test]
+fn test_tiles() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            });
+    for index in 0..15 {
+        merger.push(&generate_colored_square(index));
+    }
+
+    let tiles: Vec<_> = merger.tiles().collect();
+    assert_eq!(tiles.len(), 15);
+    for (index, tile) in tiles {
+        assert_eq!(tile.offsets(), merger.coordinates_of_index(index));
+        assert_eq!(tile.to_image(), *generate_colored_square(index as u8));
+    }
+}
+
+#[test]
+fn test_insert_image() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    for index in 0..3 {
+        merger.push(&generate_colored_square(index));
+    }
+
+    // The first row is full, so shifting the images needs a new row.
+    merger.insert_image(1, &generate_colored_square(9)).unwrap();
+    merger.insert_image(4, &generate_colored_square(8)).unwrap();
+
+    assert_eq!(merger.get_num_images(), 5);
+    assert_eq!(merger.get_num_rows(), 2);
+    for (index, expected) in [0, 9, 1, 2, 8].into_iter().enumerate() {
+        assert_eq!(
+            merger.get_image_at(index as u32),
+            Some(generate_colored_square(expected))
+        );
+    }
+
+    assert!(matches!(
+        merger.insert_image(6, &generate_test_square()),
+        Err(MergeError::IndexOutOfBounds {
+            index: 6,
+            num_images: 5
+        })
+    ));
+    assert!(matches!(
+        merger.insert_image(0, &RgbaImageBuffer::new(1, 1)),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_image_at(0), Some(generate_colored_square(0)));
+}
+
+#[test]
+fn test_swap_images() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    for index in 0..5 {
+        merger.push(&generate_colored_square(index));
+    }
+    let canvas_ptr = merger.get_canvas().as_raw().as_ptr();
+
+    merger.swap_images(0, 4).unwrap();
+    merger.swap_images(2, 2).unwrap();
+    for (index, expected) in [4, 1, 2, 3, 0].into_iter().enumerate() {
+        assert_eq!(
+            merger.get_image_at(index as u32),
+            Some(generate_colored_square(expected))
+        );
+    }
+    assert_eq!(merger.get_canvas().as_raw().as_ptr(), canvas_ptr);
+
+    assert!(matches!(
+        merger.swap_images(1, 5),
+        Err(MergeError::IndexOutOfBounds {
+            index: 5,
+            num_images: 5
+        })
+    ));
+    assert_eq!(merger.get_image_at(1), Some(generate_colored_square(1)));
+}
+
+#[test]
+fn test_layout_dimensions() {
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    for num_images in [0, 1, IMAGES_PER_ROW, TOTAL_IMAGES] {
+        let merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, num_images)
+                .with_padding(padding);
+
+        assert_eq!(
+            GrowableMerger::<Rgba<u8>>::layout_dimensions(
+                (IMAGE_WIDTH, IMAGE_HEIGHT),
+                IMAGES_PER_ROW,
+                num_images,
+                padding
+            ),
+            merger.get_canvas_dimensions()
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "too large")]
+fn test_layout_dimensions_overflow() {
+    // The width of the grid does not fit in a u32, even though every argument does.
+    GrowableMerger::<Rgba<u8>>::layout_dimensions((1 << 20, 1), 1 << 12, 1, Padding { x: 0, y: 0 });
+}
+
+#[test]
+#[should_panic(expected = "too large")]
+fn test_padding_overflow() {
+    let _ = GrowableMerger::<Rgba<u8>>::new((IMAGE_WIDTH, IMAGE_HEIGHT), 5).with_padding(Padding {
+        x: u32::MAX / 4,
+        y: 0,
+    });
+}
+
+#[test]
+fn test_max_canvas_dimensions() {
+    let square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            })
+            .with_max_canvas_dimensions((u32::MAX, IMAGE_HEIGHT * 2 + PADDING_Y));
+
+    merger.bulk_push(&[&square; 15]);
+    assert!(matches!(
+        merger.try_bulk_push(&[&square; 10]),
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: Some(_)
+        })
+    ));
+    merger.bulk_push(&[&square; 5]);
+    assert!(matches!(
+        merger.try_push(&square),
+        Err(MergeError::CanvasTooLarge { .. })
+    ));
+    assert_eq!(merger.get_num_images(), 20);
+    assert_eq!(merger.get_num_rows(), 2);
+
+    // Resetting can not grow past the limit either.
+    assert!(matches!(
+        merger.reset_to(TOTAL_IMAGES),
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: Some(_)
+        })
+    ));
+    assert_eq!(merger.get_num_images(), 0);
+    assert_eq!(
+        merger.get_canvas_dimensions().1,
+        IMAGE_HEIGHT * 2 + PADDING_Y
+    );
+}
+
+#[test]
+fn test_canvas_too_large_to_allocate() {
+    // Three rows of padding this large do not fit in a u32.
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((1, 1), 1).with_padding(Padding {
+            x: 0,
+            y: u32::MAX / 2,
+        });
+    let pixel = RgbaImageBuffer::new(1, 1);
+
+    assert!(matches!(
+        merger.try_bulk_push(&[&pixel; 3]),
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: None
+        })
+    ));
+    merger.push(&pixel);
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[test]
+fn test_append() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4);
+    let mut other: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    for index in 0..3 {
+        merger.push(&generate_colored_square(index));
+    }
+    for index in 3..10 {
+        other.push(&generate_colored_square(index));
+    }
+
+    merger.append(other).unwrap();
+    assert_eq!(merger.get_num_images(), 10);
+    assert_eq!(merger.get_num_rows(), 3);
+    for index in 0..10 {
+        assert_eq!(
+            merger.get_image_at(index),
+            Some(generate_colored_square(index as u8))
+        );
+    }
+
+    // The merger keeps counting from the end of the appended images.
+    merger.push(&generate_colored_square(10));
+    assert_eq!(merger.get_image_at(10), Some(generate_colored_square(10)));
+}
+
+#[test]
+fn test_append_mismatch() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4);
+    assert!(matches!(
+        merger.append(GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 5)),
+        Err(MergeError::ImagesPerRowMismatch {
+            expected: 4,
+            got: 5
+        })
+    ));
+    assert!(matches!(
+        merger.append(GrowableMerger::new((IMAGE_WIDTH, 1), 4)),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_borders() {
+    let border = Rgba([0, 255, 0, 255]);
+    let thickness = 2;
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        })
+        .with_borders(thickness, border);
+    for index in 0..5 {
+        merger.push(&generate_colored_square(index));
+    }
+
+    let cell_width = IMAGE_WIDTH + thickness * 2;
+    let cell_height = IMAGE_HEIGHT + thickness * 2;
+    assert_eq!(
+        merger.get_canvas_dimensions(),
+        (cell_width * 3 + PADDING_X * 2, cell_height * 2 + PADDING_Y)
+    );
+
+    // Removing an image keeps the border of its place, including in the row that was added while growing.
+    merger.remove_image(0).unwrap();
+    for index in 0..6 {
+        let (x, y) = merger.coordinates_of_index(index);
+        let canvas = merger.get_canvas();
+        assert_eq!(canvas.get_pixel(x - 1, y - 1), &border);
+        assert_eq!(
+            canvas.get_pixel(x - thickness, y + IMAGE_HEIGHT / 2),
+            &border
+        );
+        assert_eq!(
+            canvas.get_pixel(x + IMAGE_WIDTH, y + IMAGE_HEIGHT + 1),
+            &border
+        );
+        assert_eq!(merger.index_at_pixel(x - 1, y), None);
+        assert_eq!(merger.index_at_pixel(x, y), (index < 4).then_some(index));
+    }
+    for index in 0..4 {
+        assert_eq!(
+            merger.get_image_at(index),
+            Some(generate_colored_square(index as u8 + 1))
+        );
+    }
+
+    // The padding between bordered places is left as background.
+    let (x, y) = merger.coordinates_of_index(0);
+    assert_eq!(
+        merger
+            .get_canvas()
+            .get_pixel(x + IMAGE_WIDTH + thickness, y),
+        &Rgba([0, 0, 0, 0])
+    );
+}
+
+#[test]
+fn test_progress_callback() {
+    let progress = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let square = generate_test_square();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    let reported = progress.clone();
+    merger.set_progress_callback(move |done, total| reported.lock().unwrap().push((done, total)));
+    merger.bulk_push(&vec![&square; TOTAL_IMAGES as usize]);
+
+    let calls = std::mem::take(&mut *progress.lock().unwrap());
+    assert!(!calls.is_empty());
+    assert!(calls.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(calls.iter().all(|&(_, total)| total == TOTAL_IMAGES));
+    assert_eq!(calls.last(), Some(&(TOTAL_IMAGES, TOTAL_IMAGES)));
+    assert_eq!(
+        merger.get_canvas().as_raw(),
+        merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES).as_raw()
+    );
+
+    merger.remove_progress_callback();
+    merger.push(&square);
+    merger.bulk_push(&[&square; 3]);
+    assert!(progress.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_push_with_transform() {
+    let square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 6);
+
+    let transforms = [
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+    ];
+    for transform in transforms {
+        merger.push_with_transform(&square, transform).unwrap();
+    }
+    merger.push(&square);
+
+    let tile = |index| merger.get_image_at(index).unwrap();
+    let (max_x, max_y) = (IMAGE_WIDTH - 1, IMAGE_HEIGHT - 1);
+    assert_eq!(tile(0).get_pixel(max_x - 7, 3), square.get_pixel(3, 7));
+    assert_eq!(
+        tile(1).get_pixel(max_x - 3, max_y - 7),
+        square.get_pixel(3, 7)
+    );
+    assert_eq!(tile(2).get_pixel(7, max_y - 3), square.get_pixel(3, 7));
+    assert_eq!(tile(3).get_pixel(max_x - 3, 7), square.get_pixel(3, 7));
+    assert_eq!(tile(4).get_pixel(3, max_y - 7), square.get_pixel(3, 7));
+    assert_eq!(tile(5), square);
+}
+
+#[test]
+fn test_push_with_transform_non_square() {
+    let tall = RgbaImageBuffer::new(IMAGE_HEIGHT / 2, IMAGE_WIDTH);
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT / 2), IMAGES_PER_ROW);
+
+    merger
+        .push_with_transform(&tall, Transform::Rotate270)
+        .unwrap();
+    assert!(matches!(
+        merger.push_with_transform(&tall, Transform::FlipHorizontal),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[test]
+fn test_remove_images() {
+    let build = || {
+        let mut merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4).with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            });
+        for index in 0..11 {
+            merger.push(&generate_colored_square(index));
+        }
+        merger
+    };
+
+    let mut batched = build();
+    assert_eq!(batched.remove_images(&[9, 2, 0, 2, 7, 42]), 4);
+
+    let mut one_by_one = build();
+    for index in [9, 7, 2, 0] {
+        one_by_one.remove_image(index).unwrap();
+    }
+
+    assert_eq!(batched.get_num_images(), 7);
+    assert_eq!(
+        batched.get_canvas().as_raw(),
+        one_by_one.get_canvas().as_raw()
+    );
+    for (index, expected) in [1, 3, 4, 5, 6, 8, 10].into_iter().enumerate() {
+        assert_eq!(
+            batched.get_image_at(index as u32),
+            Some(generate_colored_square(expected))
+        );
+    }
+
+    assert_eq!(batched.remove_images(&[]), 0);
+    assert_eq!(batched.remove_images(&[7]), 0);
+    batched.push(&generate_colored_square(11));
+    assert_eq!(batched.get_image_at(7), Some(generate_colored_square(11)));
+}
+
+#[test]
+fn test_will_grow_on_next_push() {
+    let square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 15);
+
+    for _ in 0..20 {
+        assert!(!merger.will_grow_on_next_push());
+        merger.push(&square);
+    }
+    assert!(merger.will_grow_on_next_push());
+
+    let canvas_len = merger.get_canvas().as_raw().len();
+    merger.push(&square);
+    assert!(merger.get_canvas().as_raw().len() > canvas_len);
+    assert!(!merger.will_grow_on_next_push());
+
+    let mut fixed: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 1, 1);
+    fixed.push(&square);
+    assert!(!fixed.will_grow_on_next_push());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            })
+            .with_background(Rgba([1, 2, 3, 255]));
+    for index in 0..15 {
+        merger.push(&generate_colored_square(index));
+    }
+
+    let json = serde_json::to_string(&merger).unwrap();
+    let mut restored: GrowableMerger<Rgba<u8>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.get_num_images(), 15);
+    assert_eq!(restored.get_canvas().as_raw(), merger.get_canvas().as_raw());
+
+    // The restored merger picks up where the original left off.
+    merger.push(&generate_colored_square(15));
+    restored.push(&generate_colored_square(15));
+    assert_eq!(restored.get_canvas().as_raw(), merger.get_canvas().as_raw());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_rejects_wrong_buffer_length() {
+    let merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((2, 2), 2);
+    let mut value = serde_json::to_value(&merger).unwrap();
+    value["canvas"].as_array_mut().unwrap().pop();
+
+    assert!(serde_json::from_value::<GrowableMerger<Rgba<u8>>>(value).is_err());
+}
+
+#[test]
+fn test_blend_modes() {
+    let base = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([200u8, 100, 50]));
+    let layer = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([100u8, 255, 0]));
+
+    let expected = [
+        (BlendMode::Over, Rgb([100, 255, 0])),
+        (BlendMode::Add, Rgb([255, 255, 50])),
+        (BlendMode::Multiply, Rgb([78, 100, 0])),
+        (BlendMode::Screen, Rgb([222, 255, 50])),
+    ];
+    for (mode, pixel) in expected {
+        let mut merger: GrowableMerger<Rgb<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+        merger.push(&base);
+        merger.push(&base);
+        merger.replace_image_blended(1, &layer, mode).unwrap();
+
+        assert_eq!(merger.get_canvas().get_pixel(IMAGE_WIDTH + 50, 50), &pixel);
+        assert_eq!(merger.get_image_at(0).unwrap().as_raw(), base.as_raw());
+    }
+}
+
+#[test]
+fn test_push_blended_over_background() {
+    let half_red =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 128]));
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2).with_background(Rgba([0, 0, 255, 255]));
+
+    merger.push_blended(&half_red, BlendMode::Over).unwrap();
+    merger.push_blended(&half_red, BlendMode::Add).unwrap();
+    merger.push_blended(&half_red, BlendMode::Multiply).unwrap();
+    assert_eq!(merger.get_num_images(), 3);
+    assert_eq!(merger.get_num_rows(), 2);
+
+    let canvas = merger.get_canvas();
+    let over = canvas.get_pixel(0, 0);
+    assert!(over[0] > 120 && over[0] < 135 && over[2] > 120 && over[2] < 135);
+    // The image crate rounds the blended alpha down.
+    assert!(over[3] >= 254);
+    assert_eq!(canvas.get_pixel(IMAGE_WIDTH, 0), &Rgba([255, 0, 255, 255]));
+    assert_eq!(canvas.get_pixel(0, IMAGE_HEIGHT), &Rgba([0, 0, 0, 255]));
+
+    assert!(matches!(
+        merger.replace_image_blended(3, &half_red, BlendMode::Over),
+        Err(MergeError::IndexOutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn test_apply_watermark() {
+    let black = Rgba([0, 0, 0, 255]);
+    let watermark = RgbaImageBuffer::new_from_pixel(30, 20, Rgba([255, 255, 255, 255]));
+    let new_merger = || {
+        let mut merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3).with_background(black);
+        merger.push(&RgbaImageBuffer::new_from_pixel(
+            IMAGE_WIDTH,
+            IMAGE_HEIGHT,
+            black,
+        ));
+        merger
+    };
+
+    // A watermark in the corner only covers its own area, including the empty places of the canvas.
+    let mut merger = new_merger();
+    merger.apply_watermark(&watermark, WatermarkPosition::BottomRight, 1.0);
+    let canvas = merger.get_canvas();
+    let (width, height) = canvas.dimensions();
+    assert_eq!(
+        canvas.get_pixel(width - 30, height - 20),
+        &Rgba([255, 255, 255, 255])
+    );
+    assert_eq!(canvas.get_pixel(width - 31, height - 20), &black);
+    assert_eq!(canvas.get_pixel(width - 30, height - 21), &black);
+    assert_eq!(merger.get_num_images(), 1);
+
+    // A tiled watermark at half opacity covers everything, mixing it halfway with the canvas.
+    let mut merger = new_merger();
+    merger.apply_watermark(&watermark, WatermarkPosition::Tiled, 0.5);
+    let canvas = merger.get_canvas();
+    assert!(canvas
+        .pixels()
+        .all(|pixel| pixel == &Rgba([128, 128, 128, 255])));
+    assert_eq!(merger.get_num_images(), 1);
+
+    // A centered watermark that is larger than the canvas is cut off, and a transparent one changes nothing.
+    let mut merger = new_merger();
+    let large = RgbaImageBuffer::new_from_pixel(width * 2, height * 2, Rgba([255, 0, 0, 0]));
+    merger.apply_watermark(&large, WatermarkPosition::Center, 1.0);
+    assert!(merger.get_canvas().pixels().all(|pixel| pixel == &black));
+}
+
+#[test]
+fn test_push_converting() {
+    let rgb: BufferedImage<Rgb<u8>> =
+        BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([10, 20, 30]));
+    let luma: BufferedImage<Luma<u8>> =
+        BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Luma([40]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.push_converting(&rgb).unwrap();
+    merger.push_converting(&luma).unwrap();
+    merger.push_converting(&rgb).unwrap();
+    assert_eq!(merger.get_num_images(), 3);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(0, 0), &Rgba([10, 20, 30, 255]));
+    assert_eq!(canvas.get_pixel(IMAGE_WIDTH, 0), &Rgba([40, 40, 40, 255]));
+    assert_eq!(canvas.get_pixel(0, IMAGE_HEIGHT), &Rgba([10, 20, 30, 255]));
+
+    let small: BufferedImage<Rgb<u8>> = BufferedImage::new(IMAGE_WIDTH / 2, IMAGE_HEIGHT);
+    assert!(matches!(
+        merger.push_converting(&small),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_from_images() {
+    let squares: Vec<RgbaImageBuffer> = (0..TOTAL_IMAGES).map(|_| generate_test_square()).collect();
+    let refs: Vec<&RgbaImageBuffer> = squares.iter().collect();
+
+    let merger = GrowableMerger::from_images(&refs, IMAGES_PER_ROW).unwrap();
+    assert_eq!(merger.get_image_dimensions(), (IMAGE_WIDTH, IMAGE_HEIGHT));
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES);
+    assert_eq!(
+        merger.get_canvas_dimensions().1,
+        IMAGE_HEIGHT * TOTAL_IMAGES.div_ceil(IMAGES_PER_ROW)
+    );
+    assert_eq!(
+        merger.get_canvas().as_raw(),
+        merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES).as_raw()
+    );
+}
+
+#[test]
+fn test_from_images_errors() {
+    assert!(matches!(
+        GrowableMerger::<Rgba<u8>>::from_images(&[], IMAGES_PER_ROW),
+        Err(MergeError::NoImages)
+    ));
+
+    let square = generate_test_square();
+    let small = RgbaImageBuffer::new(1, 1);
+    assert!(matches!(
+        GrowableMerger::from_images(&[&square, &small], IMAGES_PER_ROW),
+        Err(MergeError::DimensionMismatch {
+            expected: (100, 100),
+            got: (1, 1)
+        })
+    ));
+
+    assert!(matches!(
+        GrowableMerger::from_images(&[&square], 0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+    let empty = RgbaImageBuffer::new(0, 10);
+    assert!(matches!(
+        GrowableMerger::from_images(&[&empty], IMAGES_PER_ROW),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_empty_merger_has_one_row() {
+    let square = generate_test_square();
+    for expected_images in [0, 1] {
+        let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::with_capacity(
+            (IMAGE_WIDTH, IMAGE_HEIGHT),
+            IMAGES_PER_ROW,
+            expected_images,
+        );
+        assert_eq!(
+            merger.get_canvas_dimensions(),
+            (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT)
+        );
+
+        // The first row already exists, so filling it never grows the canvas.
+        let canvas_ptr = merger.get_canvas().as_raw().as_ptr();
+        for _ in 0..IMAGES_PER_ROW {
+            assert!(!merger.will_grow_on_next_push());
+            merger.push(&square);
+        }
+        assert_eq!(merger.get_canvas().as_raw().as_ptr(), canvas_ptr);
+        assert_eq!(merger.get_canvas_dimensions().1, IMAGE_HEIGHT);
+        assert!(merger.will_grow_on_next_push());
+    }
+}
+
+#[test]
+fn test_estimated_bytes() {
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+    let mut merger: GrowableMerger<Luma<f32>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW).with_padding(padding);
+    let image = BufferedImage::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    merger.bulk_push(&[&image; 25]);
+
+    let expected = GrowableMerger::<Luma<f32>>::estimate_bytes_for(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        25,
+        padding,
+    );
+    assert_eq!(merger.estimated_bytes(), expected);
+    assert_eq!(
+        merger.estimated_bytes(),
+        merger.get_canvas().as_raw().len() * std::mem::size_of::<f32>()
+    );
+}
+
+#[test]
+fn test_builder_matches_setters() {
+    let test_square = generate_test_square();
+    let background = Rgba([10, 20, 30, 255]);
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    let mut built: GrowableMerger<Rgba<u8>> = GrowableMerger::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(IMAGES_PER_ROW)
+        .capacity(TOTAL_IMAGES)
+        .padding(padding)
+        .background(Background::Solid(background))
+        .growth_strategy(GrowthStrategy::Double)
+        .build()
+        .unwrap();
+    let mut manual: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES)
+            .with_padding(padding)
+            .with_background(background)
+            .with_growth_strategy(GrowthStrategy::Double);
+
+    for _ in 0..TOTAL_IMAGES + 1 {
+        built.push(&test_square);
+        manual.push(&test_square);
+    }
+
+    assert_eq!(
+        built.get_canvas().dimensions(),
+        manual.get_canvas().dimensions()
+    );
+    assert!(built.get_canvas().pixels().eq(manual.get_canvas().pixels()));
+}
+
+#[test]
+fn test_builder_invalid_configuration() {
+    let missing_dimensions = GrowableMerger::<Rgba<u8>>::builder()
+        .images_per_row(IMAGES_PER_ROW)
+        .build();
+    assert!(matches!(
+        missing_dimensions,
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let missing_per_row = GrowableMerger::<Rgba<u8>>::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .build();
+    assert!(matches!(
+        missing_per_row,
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let zero_per_row = GrowableMerger::<Rgba<u8>>::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(0)
+        .build();
+    assert!(matches!(
+        zero_per_row,
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let zero_checker = GrowableMerger::<Rgba<u8>>::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(IMAGES_PER_ROW)
+        .background(Background::Checker {
+            a: Rgba([0, 0, 0, 255]),
+            b: Rgba([255, 255, 255, 255]),
+            size: 0,
+        })
+        .build();
+    assert!(matches!(
+        zero_checker,
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let too_large = GrowableMerger::<Rgba<u8>>::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(IMAGES_PER_ROW)
+        .capacity(TOTAL_IMAGES)
+        .max_canvas_dimensions((IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT * 5))
+        .build();
+    assert!(matches!(
+        too_large,
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: Some(_)
+        })
+    ));
+
+    // The builder rejects the same memory budgets that `with_memory_budget` panics on.
+    let column_major_budget = GrowableMerger::<Rgba<u8>>::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(IMAGES_PER_ROW)
+        .fill_order(FillOrder::ColumnMajor)
+        .memory_budget(usize::MAX, |_| {})
+        .build();
+    assert!(matches!(
+        column_major_budget,
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let tiny_budget = GrowableMerger::<Rgba<u8>>::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(IMAGES_PER_ROW)
+        .memory_budget(1, |_| {})
+        .build();
+    assert!(matches!(
+        tiny_budget,
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn test_occupied_cells() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES);
+    assert_eq!(merger.occupied_cells(), 0..0);
+    assert!(!merger.is_cell_occupied(0));
+
+    merger.bulk_push(&[&test_square; 5]);
+    assert_eq!(merger.occupied_cells(), 0..5);
+    assert!(merger.is_cell_occupied(4));
+    assert!(!merger.is_cell_occupied(5));
+
+    merger.remove_image(1);
+    assert_eq!(merger.occupied_cells(), 0..4);
+    assert!(!merger.is_cell_occupied(4));
+}
+
+#[test]
+fn test_downscaled() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&[&test_square; 25]);
+    let dimensions = merger.get_canvas().dimensions();
+    assert_eq!(dimensions, (1000, 300));
+
+    let preview = merger.downscaled(100, image::imageops::FilterType::Triangle);
+    assert_eq!(preview.dimensions(), (100, 30));
+    assert_eq!(merger.get_canvas().dimensions(), dimensions);
+
+    let copy = merger.downscaled(2000, image::imageops::FilterType::Triangle);
+    assert_eq!(copy.dimensions(), dimensions);
+    assert!(copy.pixels().eq(merger.get_canvas().pixels()));
+}
+
+#[test]
+fn test_with_canvas() {
+    let test_square = generate_test_square();
+    let mut buffer = image::RgbaImage::new(IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT * 2);
+    buffer
+        .pixels_mut()
+        .for_each(|pixel| *pixel = Rgba([255; 4]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_canvas(buffer, (IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW).unwrap();
+    assert_eq!(merger.get_num_images(), 0);
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .all(|pixel| *pixel == Rgba([0; 4])));
+
+    merger.bulk_push(&[&test_square; 20]);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT * 2)
+    );
+    merger.push(&test_square);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT * 3)
+    );
+
+    let empty = image::RgbaImage::new(IMAGE_WIDTH * IMAGES_PER_ROW, 0);
+    let merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_canvas(empty, (IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW).unwrap();
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT)
+    );
+
+    let wrong_height = image::RgbaImage::new(IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT + 1);
+    let result: Result<GrowableMerger<Rgba<u8>>, MergeError> =
+        GrowableMerger::with_canvas(wrong_height, (IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert!(matches!(
+        result,
+        Err(MergeError::DimensionMismatch {
+            expected: (1000, 200),
+            got: (1000, 101)
+        })
+    ));
+}
+
+#[test]
+fn test_holes_are_not_occupied_tiles() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&[&test_square; 3]);
+    merger.push_at(5, &test_square).unwrap();
+    merger
+        .remove_image_with_mode(1, RemovalMode::Leave)
+        .unwrap();
+
+    // Indices 1, 3 and 4 are holes, so only 0, 2 and 5 hold images.
+    let occupied = [0, 2, 5];
+    assert_eq!(
+        merger
+            .iter_coordinates()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>(),
+        occupied
+    );
+    assert_eq!(
+        merger.tiles().map(|(index, _)| index).collect::<Vec<_>>(),
+        occupied
+    );
+    assert_eq!(merger.get_image_at(1), None);
+    assert_eq!(merger.get_image_at(4), None);
+    assert!(merger.get_image_at(5).is_some());
+
+    let dir = std::env::temp_dir().join("image_merger_holes_are_not_occupied_tiles");
+    std::fs::create_dir_all(&dir).unwrap();
+    merger
+        .export_tiles(&dir, |index| format!("{}.png", index))
+        .unwrap();
+    let mut exported: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    exported.sort();
+    assert_eq!(exported, ["0.png", "2.png", "5.png"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_push_at() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    merger.push_at(25, &test_square).unwrap();
+    assert_eq!(merger.get_canvas().dimensions(), (1000, 300));
+    assert_eq!(merger.get_num_images(), 26);
+    assert_eq!(merger.last_pasted_index(), Some(25));
+    assert_eq!(
+        merger.get_image_at(25).unwrap().as_raw(),
+        test_square.as_raw()
+    );
+    // The places skipped over are holes, which hold no image.
+    assert_eq!(merger.get_image_at(3), None);
+
+    // Pasting before the highest index does not move where the next push goes.
+    merger.push_at(3, &test_square).unwrap();
+    assert_eq!(merger.last_pasted_index(), Some(25));
+    assert_eq!(
+        merger.get_image_at(3).unwrap().as_raw(),
+        test_square.as_raw()
+    );
+
+    merger.push(&test_square);
+    assert_eq!(merger.last_pasted_index(), Some(26));
+
+    let mut grid: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2);
+    assert!(matches!(
+        grid.push_at(4, &test_square),
+        Err(MergeError::OutOfCapacity { capacity: 4 })
+    ));
+    assert!(matches!(
+        merger.push_at(u32::MAX, &test_square),
+        Err(MergeError::CanvasTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_trim_transparent() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES);
+    merger.bulk_push(&[&test_square; 15]);
+
+    let trimmed = merger.trim_transparent();
+    assert_eq!(trimmed.dimensions(), (1000, 200));
+    assert_eq!(
+        trimmed.as_raw()[..],
+        merger.get_canvas().as_raw()[..trimmed.as_raw().len()]
+    );
+
+    // A transparent image in an occupied place is never cut off.
+    let transparent = RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    merger.push_at(35, &transparent).unwrap();
+    assert_eq!(merger.trim_transparent().dimensions(), (1000, 400));
+
+    let empty: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert_eq!(empty.trim_transparent().dimensions(), (1000, 0));
+
+    let with_background: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_background(Rgba([0, 0, 0, 255]));
+    assert_eq!(with_background.trim_transparent().dimensions(), (1000, 100));
+}
+
+#[test]
+fn test_caption_height() {
+    let test_square = generate_test_square();
+    let background = Rgba([10, 20, 30, 255]);
+    let border = Rgba([200, 0, 0, 255]);
+    let caption_height = 20;
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            })
+            .with_background(background)
+            .with_borders(2, border)
+            .with_caption_height(caption_height);
+    merger.bulk_push(&[&test_square; 15]);
+
+    let cell_height = IMAGE_HEIGHT + 4 + caption_height;
+    assert_eq!(merger.get_canvas().height(), cell_height * 2 + PADDING_Y);
+
+    // The second row starts below the caption strip of the first.
+    let (x, y) = merger.coordinates_of_index(IMAGES_PER_ROW);
+    assert_eq!((x, y), (2, cell_height + PADDING_Y + 2));
+    assert_eq!(
+        merger.get_image_at(12).unwrap().as_raw(),
+        test_square.as_raw()
+    );
+
+    // The caption strip is below the border and holds the background.
+    let (caption_x, caption_y) = merger.caption_coordinates_of_index(IMAGES_PER_ROW);
+    assert_eq!((caption_x, caption_y), (0, y + IMAGE_HEIGHT + 2));
+    assert_eq!(
+        *merger.get_canvas().get_pixel(caption_x, caption_y - 1),
+        border
+    );
+    for offset_y in 0..caption_height {
+        for offset_x in 0..IMAGE_WIDTH + 4 {
+            assert_eq!(
+                *merger
+                    .get_canvas()
+                    .get_pixel(caption_x + offset_x, caption_y + offset_y),
+                background
+            );
+        }
+    }
+    assert_eq!(merger.index_at_pixel(caption_x + 10, caption_y), None);
+}
+
+#[test]
+fn test_reflow() {
+    let images: Vec<RgbaImageBuffer> = (0..TOTAL_IMAGES as u8)
+        .map(|i| RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([i, 0, 0, 255])))
+        .collect();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            })
+            .with_borders(1, Rgba([255; 4]));
+    merger.bulk_push(&images.iter().collect::<Vec<_>>());
+
+    merger.reflow(7).unwrap();
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES);
+    assert_eq!(merger.get_num_rows(), TOTAL_IMAGES.div_ceil(7));
+    assert_eq!(
+        merger.get_canvas().width(),
+        7 * (IMAGE_WIDTH + 2) + 6 * PADDING_X
+    );
+    for (index, image) in images.iter().enumerate() {
+        assert_eq!(
+            merger.get_image_at(index as u32).unwrap().as_raw(),
+            image.as_raw()
+        );
+    }
+
+    merger.reflow(IMAGES_PER_ROW * 2).unwrap();
+    assert_eq!(
+        merger.get_num_rows(),
+        TOTAL_IMAGES.div_ceil(IMAGES_PER_ROW * 2)
+    );
+    assert_eq!(
+        merger.get_image_at(94).unwrap().as_raw(),
+        images[94].as_raw()
+    );
+
+    assert!(matches!(
+        merger.reflow(0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let mut limited: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_max_canvas_dimensions((IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT * 2));
+    assert!(matches!(
+        limited.reflow(2),
+        Err(MergeError::CanvasTooLarge { .. })
+    ));
+    assert_eq!(
+        limited.get_canvas().dimensions(),
+        (IMAGE_WIDTH * IMAGES_PER_ROW, IMAGE_HEIGHT)
+    );
+}
+
+#[test]
+fn test_capacity() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert_eq!(merger.capacity(), IMAGES_PER_ROW);
+    assert_eq!(merger.remaining_capacity(), IMAGES_PER_ROW);
+
+    merger.bulk_push(&[&test_square; 10]);
+    assert_eq!(merger.remaining_capacity(), 0);
+    assert!(merger.will_grow_on_next_push());
+
+    merger.push(&test_square);
+    assert_eq!(merger.capacity(), IMAGES_PER_ROW * 2);
+    assert_eq!(merger.remaining_capacity(), IMAGES_PER_ROW - 1);
+
+    merger.shrink_to_fit().unwrap();
+    assert_eq!(merger.capacity(), IMAGES_PER_ROW * 2);
+    merger.remove_image(0);
+    merger.shrink_to_fit().unwrap();
+    assert_eq!(merger.capacity(), IMAGES_PER_ROW);
+    assert_eq!(merger.remaining_capacity(), 0);
+}
+
+#[test]
+fn test_u16_canvas() {
+    let image: BufferedImage<Rgba<u16>> =
+        BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([65535, 1234, 0, 40000]));
+    let mut merger: GrowableMerger<Rgba<u16>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3).with_background(Rgba([1, 2, 3, 65535]));
+    merger.bulk_push(&[&image; 4]);
+    merger.push(&image);
+    assert_eq!(merger.get_image_at(4).unwrap().as_raw(), image.as_raw());
+    assert_eq!(
+        *merger.get_canvas().get_pixel(299, 199),
+        Rgba([1, 2, 3, 65535])
+    );
+
+    let bytes = merger.encode(image::ImageFormat::Png).unwrap();
+    let decoded = image::load_from_memory(&bytes).unwrap().into_rgba16();
+    assert_eq!(decoded.as_raw(), merger.get_canvas().as_raw());
+
+    merger.push_blended(&image, BlendMode::Add).unwrap();
+    assert_eq!(
+        *merger.get_canvas().get_pixel(2 * IMAGE_WIDTH, IMAGE_HEIGHT),
+        Rgba([65535, 1236, 3, 65535])
+    );
+}
+
+#[test]
+fn test_f32_canvas() {
+    // Values outside of 0.0 to 1.0 are kept, for high dynamic range canvases.
+    let image: BufferedImage<Rgba<f32>> =
+        BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([4.5, 0.25, -0.5, 1.0]));
+    let mut merger: GrowableMerger<Rgba<f32>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    merger.bulk_push(&[&image; 2]);
+    merger.push(&image);
+    merger.remove_image(0);
+    assert_eq!(merger.get_num_images(), 2);
+    assert_eq!(merger.get_image_at(1).unwrap().as_raw(), image.as_raw());
+    assert_eq!(
+        *merger.get_canvas().get_pixel(2 * IMAGE_WIDTH, 0),
+        Rgba([0.0; 4])
+    );
+
+    merger.push_blended(&image, BlendMode::Add).unwrap();
+    assert_eq!(
+        *merger.get_canvas().get_pixel(2 * IMAGE_WIDTH, 0),
+        Rgba([4.5, 0.25, -0.5, 1.0])
+    );
+    merger
+        .replace_image_blended(2, &image, BlendMode::Add)
+        .unwrap();
+    assert_eq!(
+        *merger.get_canvas().get_pixel(2 * IMAGE_WIDTH, 0),
+        Rgba([9.0, 0.5, -1.0, 1.0])
+    );
+
+    let bytes = merger.encode(image::ImageFormat::OpenExr).unwrap();
+    let decoded = image::load_from_memory(&bytes).unwrap().into_rgba32f();
+    assert_eq!(decoded.as_raw(), merger.get_canvas().as_raw());
+}
+
+#[test]
+fn test_fill_cell() {
+    let test_square = generate_test_square();
+    let gray = Rgba([128, 128, 128, 255]);
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_borders(1, Rgba([255; 4]));
+
+    merger.push(&test_square);
+    merger.fill_cell(1, gray).unwrap();
+    assert_eq!(merger.get_num_images(), 2);
+    assert!(merger
+        .get_image_at(1)
+        .unwrap()
+        .pixels()
+        .all(|pixel| *pixel == gray));
+
+    // The next push goes after the filled place, and a place past the end grows the canvas.
+    merger.push(&test_square);
+    assert_eq!(merger.last_pasted_index(), Some(2));
+    merger.fill_cell(15, gray).unwrap();
+    assert_eq!(merger.get_num_images(), 16);
+    assert_eq!(merger.get_canvas().height(), 2 * (IMAGE_HEIGHT + 2));
+    let (x, y) = merger.coordinates_of_index(15);
+    assert_eq!(*merger.get_canvas().get_pixel(x - 1, y), Rgba([255; 4]));
+
+    let mut grid: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 1);
+    assert!(matches!(
+        grid.fill_cell(2, gray),
+        Err(MergeError::OutOfCapacity { capacity: 2 })
+    ));
+}
+
+#[test]
+fn test_push_bytes() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    test_square
+        .write_to(&mut bytes, image::ImageFormat::Png)
+        .unwrap();
+    merger.push_bytes(bytes.get_ref()).unwrap();
+    assert_eq!(
+        merger.get_image_at(0).unwrap().as_raw(),
+        test_square.as_raw()
+    );
+
+    assert!(matches!(
+        merger.push_bytes(&[1, 2, 3]),
+        Err(MergeError::Image(_))
+    ));
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+/// Runs a future to completion on the current thread, parking it while the future is pending.
+#[cfg(feature = "async")]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_push_url() {
+    let test_square = generate_test_square();
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    test_square
+        .write_to(&mut encoded, image::ImageFormat::Png)
+        .unwrap();
+    let encoded = encoded.into_inner();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    block_on(merger.push_url("https://example.com/square.png", |url| {
+        assert_eq!(url, "https://example.com/square.png");
+        let bytes = encoded.clone();
+        async move { Ok(bytes) }
+    }))
+    .unwrap();
+    assert_eq!(
+        merger.get_image_at(0).unwrap().as_raw(),
+        test_square.as_raw()
+    );
+
+    let missing = block_on(
+        merger.push_url("https://example.com/missing.png", |_| async {
+            Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+        }),
+    );
+    assert!(matches!(missing, Err(MergeError::Image(_))));
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_push_url_with_spawner() {
+    let test_square = generate_test_square();
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    test_square
+        .write_to(&mut encoded, image::ImageFormat::Png)
+        .unwrap();
+    let encoded = encoded.into_inner();
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    let caller = std::thread::current().id();
+    block_on(merger.push_url_with_spawner(
+        "https://example.com/square.png",
+        |_| async { Ok(encoded) },
+        |decode| async move {
+            std::thread::spawn(move || {
+                // The image is decoded on the spawned thread, not on the one awaiting the push.
+                assert_ne!(std::thread::current().id(), caller);
+                decode()
+            })
+            .join()
+            .unwrap()
+        },
+    ))
+    .unwrap();
+    assert_eq!(
+        merger.get_image_at(0).unwrap().as_raw(),
+        test_square.as_raw()
+    );
+
+    let garbage = block_on(merger.push_url_with_spawner(
+        "https://example.com/garbage.png",
+        |_| async { Ok(vec![1, 2, 3]) },
+        |decode| async move { decode() },
+    ));
+    assert!(matches!(garbage, Err(MergeError::Image(_))));
+    assert_eq!(merger.get_num_images(), 1);
+}
+
+#[test]
+fn test_push_iter() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+
+    let pushed = merger
+        .push_iter((0..TOTAL_IMAGES).map(|_| Image::from(test_square.clone())))
+        .unwrap();
+    assert_eq!(pushed, TOTAL_IMAGES);
+    let expected = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES);
+    assert_eq!(merger.get_canvas(), &expected);
+
+    // An iterator without a size hint still grows the canvas as it goes.
+    let filtered = (0..15)
+        .filter(|_| true)
+        .map(|_| Image::from(test_square.clone()));
+    assert_eq!(merger.push_iter(filtered).unwrap(), 15);
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES + 15);
+
+    let mut images = vec![
+        Image::from(test_square.clone()),
+        RgbaImageBuffer::new(1, 1),
+        Image::from(test_square.clone()),
+    ]
+    .into_iter();
+    assert!(matches!(
+        merger.push_iter(&mut images),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES + 16);
+    assert_eq!(images.len(), 1);
+}
+
+#[test]
+fn test_merge_grid() {
+    let test_square = generate_test_square();
+    let images: Vec<RgbaImageBuffer> = (0..TOTAL_IMAGES)
+        .map(|_| Image::from(test_square.clone()))
+        .collect();
+
+    let canvas = merge_grid(&images, IMAGES_PER_ROW).unwrap();
+    let expected = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES);
+    assert_eq!(canvas, expected.into_buffer());
+
+    // A single row is only as wide as the images in it.
+    let canvas = merge_grid(&images[..3], IMAGES_PER_ROW).unwrap();
+    assert_eq!(canvas.dimensions(), (3 * IMAGE_WIDTH, IMAGE_HEIGHT));
+
+    let empty: [RgbaImageBuffer; 0] = [];
+    assert!(matches!(
+        merge_grid(&empty, IMAGES_PER_ROW),
+        Err(MergeError::NoImages)
+    ));
+    assert!(matches!(
+        merge_grid(&images, 0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let mismatched = [Image::from(test_square.clone()), RgbaImageBuffer::new(1, 1)];
+    assert!(matches!(
+        merge_grid(&mismatched, IMAGES_PER_ROW),
+        Err(MergeError::DimensionMismatch {
+            expected: (100, 100),
+            got: (1, 1)
+        })
+    ));
+}
+
+#[test]
+fn test_remove_image_leave() {
+    let squares: Vec<RgbaImageBuffer> = (0..5).map(generate_colored_square).collect();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.bulk_push(&squares.iter().collect::<Vec<_>>());
+    assert!(merger
+        .remove_image_with_mode(1, RemovalMode::Leave)
+        .is_some());
+
+    // The removed place is left as a hole, and every other image stays where it is.
+    let empty = RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut expected: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    expected.bulk_push(&[&squares[0], &empty, &squares[2], &squares[3], &squares[4]]);
+    assert_eq!(merger.get_num_images(), 5);
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+
+    // Pushing continues after the last image instead of filling the hole.
+    merger.push(&squares[1]);
+    assert_eq!(merger.last_pasted_index(), Some(5));
+
+    // Removing the last image gives its place back.
+    merger
+        .remove_image_with_mode(5, RemovalMode::Leave)
+        .unwrap();
+    assert_eq!(merger.get_num_images(), 5);
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+
+    assert!(merger
+        .remove_image_with_mode(5, RemovalMode::Leave)
+        .is_none());
+}
+
+#[test]
+fn test_pixels_equal_and_first_diff() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20);
+    merger.bulk_push(&[&test_square; 20]);
+
+    // A different kind of merger can be compared against.
+    let mut known: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20, None);
+    known.bulk_push(&[&test_square; 20]);
+    assert!(merger.pixels_equal(&known));
+    assert_eq!(merger.first_diff(&known).unwrap(), None);
+
+    let mut other: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20);
+    other.bulk_push(&[&test_square; 12]);
+    other.push(&generate_colored_square(7));
+    other.bulk_push(&[&test_square; 7]);
+    assert!(!merger.pixels_equal(&other));
+    assert_eq!(
+        merger.first_diff(&other).unwrap(),
+        Some((2 * IMAGE_WIDTH, IMAGE_HEIGHT))
+    );
+
+    let smaller: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert!(!merger.pixels_equal(&smaller));
+}
+
+#[test]
+fn test_first_diff_dimension_mismatch() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20);
+    merger.bulk_push(&[&test_square; 20]);
+
+    // A smaller canvas is reported as a mismatch, not as a difference at the origin.
+    let smaller: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert!(matches!(
+        merger.first_diff(&smaller),
+        Err(MergeError::DimensionMismatch {
+            expected: (1000, 100),
+            got: (1000, 200)
+        })
+    ));
+}
+
+#[test]
+fn test_tight_last_row() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_background(Rgba([0, 0, 0, 255]))
+            .with_tight_last_row(true);
+    merger.bulk_push(&[&test_square; 3]);
+
+    let trimmed = merger.trim_transparent();
+    assert_eq!(trimmed.dimensions(), (3 * IMAGE_WIDTH, IMAGE_HEIGHT));
+    assert_eq!(trimmed, merge_images_slow(3, 3).into_buffer());
+
+    // A hole before the last image is never cropped.
+    merger.push_at(5, &test_square).unwrap();
+    assert_eq!(
+        merger.trim_transparent().dimensions(),
+        (6 * IMAGE_WIDTH, IMAGE_HEIGHT)
+    );
+
+    // Once there is a second row, every row is kept at its full width.
+    merger.bulk_push(&[&test_square; IMAGES_PER_ROW as usize]);
+    assert_eq!(
+        merger.trim_transparent().dimensions(),
+        merger.get_canvas().dimensions()
+    );
+
+    let mut column_major: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_fill_order(FillOrder::ColumnMajor)
+            .with_background(Rgba([0, 0, 0, 255]))
+            .with_tight_last_row(true);
+    column_major.bulk_push(&[&test_square; 3]);
+    assert_eq!(
+        column_major.trim_transparent().dimensions(),
+        (IMAGE_WIDTH, 3 * IMAGE_HEIGHT)
+    );
+}
+
+#[test]
+fn test_tight_last_row_below_full_rows() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3)
+        .with_background(Rgba([0, 0, 0, 255]))
+        .with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        })
+        .with_tight_last_row(true);
+    merger.bulk_push(&[&test_square; 7]);
+
+    // The canvas can not be narrowed below the full rows, so the last two places of the third row are cleared instead.
+    let trimmed = merger.trim_transparent();
+    let canvas = merger.get_canvas();
+    assert_eq!(trimmed.dimensions(), canvas.dimensions());
+    let cleared_x = IMAGE_WIDTH + PADDING_X;
+    let cleared_y = 2 * (IMAGE_HEIGHT + PADDING_Y);
+    for (x, y, pixel) in trimmed.enumerate_pixels() {
+        if x >= cleared_x && y >= cleared_y {
+            assert_eq!(pixel, &Rgba([0; 4]), "({}, {}) was not cleared", x, y);
+        } else {
+            assert_eq!(pixel, canvas.get_pixel(x, y), "({}, {}) changed", x, y);
+        }
+    }
+
+    // Only the places after the last image are cleared, never a hole before it.
+    merger
+        .remove_image_with_mode(5, RemovalMode::Leave)
+        .unwrap();
+    let hole_x = 2 * (IMAGE_WIDTH + PADDING_X) + IMAGE_WIDTH / 2;
+    let hole_y = IMAGE_HEIGHT + PADDING_Y + IMAGE_HEIGHT / 2;
+    let trimmed = merger.trim_transparent();
+    assert_eq!(trimmed.get_pixel(hole_x, hole_y), &Rgba([0, 0, 0, 255]));
+    assert_eq!(trimmed.get_pixel(cleared_x, cleared_y), &Rgba([0; 4]));
+}
+
+/// Installs a logger that records the events of this crate along with the thread they were emitted on, so tests running at
+/// the same time can each pick out their own events with `log_records`.
+#[cfg(feature = "log")]
+fn record_log_events() {
+    struct Recorder;
+    impl log::Log for Recorder {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target().starts_with("image_merger")
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                let mut records = LOG_RECORDS.lock().unwrap();
+                records.push((
+                    std::thread::current().id(),
+                    record.level(),
+                    record.args().to_string(),
+                ));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        log::set_logger(&Recorder).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+}
+
+#[cfg(feature = "log")]
+static LOG_RECORDS: std::sync::Mutex<Vec<(std::thread::ThreadId, log::Level, String)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Returns the events recorded on the current thread since `record_log_events` was first called.
+#[cfg(feature = "log")]
+fn log_records() -> Vec<(log::Level, String)> {
+    let thread = std::thread::current().id();
+    LOG_RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(id, _, _)| *id == thread)
+        .map(|(_, level, message)| (*level, message.clone()))
+        .collect()
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_log_events() {
+    record_log_events();
+
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.bulk_push(&[&test_square; 3]);
+
+    let records = log_records();
+    assert!(records.contains(&(
+        log::Level::Debug,
+        "Grew the canvas from 200x100 to 200x200, going from 1 to 2 lines.".to_owned()
+    )));
+    assert!(records.contains(&(
+        log::Level::Trace,
+        "Pasted the image at index 2 at (0, 100).".to_owned()
+    )));
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_bulk_push_grows_once() {
+    record_log_events();
+
+    let image: RgbaImageBuffer = RgbaImageBuffer::new(10, 10);
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((10, 10), 10);
+    merger.bulk_push(&vec![&image; 1000]);
+
+    let growths = log_records()
+        .into_iter()
+        .filter(|(_, message)| message.starts_with("Grew the canvas"))
+        .count();
+    assert_eq!(growths, 1);
+    assert_eq!(merger.get_canvas().dimensions(), (100, 1000));
+}
+
+#[test]
+fn test_header_and_footer() {
+    let test_square = generate_test_square();
+    let width = IMAGE_WIDTH * IMAGES_PER_ROW;
+    let header = RgbaImageBuffer::new_from_pixel(width, 30, Rgba([255, 0, 0, 255]));
+    let footer = RgbaImageBuffer::new_from_pixel(width / 2, 20, Rgba([0, 0, 255, 255]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_header(Image::from(header.clone()))
+            .with_footer(Image::from(footer.clone()));
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (width, 30 + IMAGE_HEIGHT + 20)
+    );
+    assert_eq!(merger.coordinates_of_index(0), (0, 30));
+
+    // Growing the canvas moves the footer down, and the grid never overlaps either band.
+    merger.bulk_push(&[&test_square; 15]);
+    merger.push(&test_square);
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.dimensions(), (width, 30 + 2 * IMAGE_HEIGHT + 20));
+
+    let grid = merger.crop_region(0, 30, width, 2 * IMAGE_HEIGHT).unwrap();
+    assert_eq!(grid, merge_images_slow(IMAGES_PER_ROW, 16).into_buffer());
+    assert_eq!(
+        merger.crop_region(0, 0, width, 30).unwrap(),
+        header.into_buffer()
+    );
+    assert_eq!(
+        merger
+            .crop_region(0, 30 + 2 * IMAGE_HEIGHT, width / 2, 20)
+            .unwrap(),
+        footer.into_buffer()
+    );
+    assert!(merger
+        .crop_region(width / 2, 30 + 2 * IMAGE_HEIGHT, width / 2, 20)
+        .unwrap()
+        .pixels()
+        .all(|pixel| *pixel == Rgba([0, 0, 0, 0])));
+
+    assert_eq!(merger.index_at_pixel(0, 10), None);
+    assert_eq!(merger.index_at_pixel(0, 30), Some(0));
+    assert_eq!(merger.index_at_pixel(0, 30 + IMAGE_HEIGHT), Some(10));
+    assert_eq!(merger.index_at_pixel(0, 30 + 2 * IMAGE_HEIGHT), None);
+}
+
+#[test]
+fn test_reserve_rows() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&[&test_square; 15]);
+
+    merger.reserve_rows(8).unwrap();
+    assert_eq!(merger.get_num_rows(), 2);
+    assert_eq!(merger.capacity(), 10 * IMAGES_PER_ROW);
+
+    // The canvas already holds enough rows, so nothing is reallocated.
+    merger.reserve_rows(3).unwrap();
+    assert_eq!(merger.capacity(), 10 * IMAGES_PER_ROW);
+    assert!(!merger.will_grow_on_next_push());
+
+    merger.bulk_push(&[&test_square; 80]);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES)
+    );
+
+    let mut fixed: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2);
+    assert!(fixed.reserve_rows(2).is_ok());
+    assert!(matches!(
+        fixed.reserve_rows(3),
+        Err(MergeError::OutOfCapacity { capacity: 4 })
+    ));
+}
+
+#[test]
+fn test_row_slice() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW).with_padding(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        });
+    merger.bulk_push(&[&test_square; 25]);
+
+    let width = merger.get_canvas().width();
+    for row in 0..3 {
+        let expected = merger
+            .crop_region(0, row * (IMAGE_HEIGHT + PADDING_Y), width, IMAGE_HEIGHT)
+            .unwrap();
+        assert_eq!(merger.row_slice(row).unwrap(), &expected.as_raw()[..]);
+    }
+    assert!(merger.row_slice(3).is_none());
+
+    let mut column_major: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_fill_order(FillOrder::ColumnMajor);
+    column_major.push(&test_square);
+    assert!(column_major.row_slice(0).is_none());
+}
+
+#[test]
+fn test_set_images_per_row() {
+    let squares: Vec<RgbaImageBuffer> = (0..7).map(generate_colored_square).collect();
+    let squares: Vec<&RgbaImageBuffer> = squares.iter().collect();
+
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.bulk_push(&squares);
+    merger.set_images_per_row(3).unwrap();
+
+    let mut expected: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    expected.bulk_push(&squares);
+    assert_eq!(merger.get_num_columns(), 3);
+    assert_eq!(merger.get_num_images(), 7);
+    assert!(merger.pixels_equal(&expected));
+
+    assert!(matches!(
+        merger.set_images_per_row(0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+    assert_eq!(merger.get_num_columns(), 3);
+}
+
+#[test]
+fn test_content_hash() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20);
+    merger.bulk_push(&[&test_square; 20]);
+
+    // The same canvas hashes the same, however it was assembled.
+    let mut known: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 20, None);
+    for _ in 0..20 {
+        known.push(&test_square);
+    }
+    assert_eq!(merger.content_hash(), known.content_hash());
+
+    merger
+        .replace_image(3, &generate_colored_square(1))
+        .unwrap();
+    assert_ne!(merger.content_hash(), known.content_hash());
+
+    // The hash is stable, so it can be stored and compared later.
+    let mut tiny: GrowableMerger<Luma<u16>> = GrowableMerger::new((1, 1), 2);
+    tiny.push(&BufferedImage::new_from_pixel(1, 1, Luma([0x1234])));
+    assert_eq!(tiny.content_hash(), 1895374842467920828);
+}
+
+#[test]
+fn test_push_region() {
+    let test_square = generate_test_square();
+    let mut atlas = RgbaImageBuffer::new(IMAGE_WIDTH * 3, IMAGE_HEIGHT);
+    for frame in 0..3 {
+        overlay(&mut *atlas, &*test_square, (frame * IMAGE_WIDTH) as i64, 0);
+    }
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    for frame in 0..3 {
+        merger
+            .push_region(&atlas, frame * IMAGE_WIDTH, 0, IMAGE_WIDTH, IMAGE_HEIGHT)
+            .unwrap();
+    }
+    assert_eq!(merger.get_canvas(), &merge_images_slow(IMAGES_PER_ROW, 3));
+
+    assert!(matches!(
+        merger.push_region(&atlas, 2 * IMAGE_WIDTH + 1, 0, IMAGE_WIDTH, IMAGE_HEIGHT),
+        Err(MergeError::RegionOutOfBounds {
+            region: (201, 0, 100, 100),
+            dimensions: (300, 100)
+        })
+    ));
+    assert!(matches!(
+        merger.push_region(&atlas, 0, 0, 50, 50),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_num_images(), 3);
+}
+
+#[test]
+fn test_iter_coordinates() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            })
+            .with_borders(2, Rgba([255, 0, 0, 255]));
+    merger.bulk_push(&[&test_square; 25]);
+
+    let rectangles: Vec<_> = merger.iter_coordinates().collect();
+    assert_eq!(rectangles.len(), 25);
+    for (index, (x, y, width, height)) in rectangles {
+        assert_eq!((x, y), merger.coordinates_of_index(index));
+        assert_eq!((width, height), (IMAGE_WIDTH, IMAGE_HEIGHT));
+        assert_eq!(merger.index_at_pixel(x, y), Some(index));
+    }
+
+    let empty: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert_eq!(empty.iter_coordinates().count(), 0);
+}
+
+#[test]
+fn test_remove_image_changes_only_its_places() {
+    let squares: Vec<RgbaImageBuffer> = (1..4).map(generate_colored_square).collect();
+    let background = Rgba([9, 9, 9, 255]);
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    for mode in [RemovalMode::Compact, RemovalMode::Leave] {
+        let mut merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2)
+                .with_padding(padding)
+                .with_background(background)
+                .with_borders(3, Rgba([255, 255, 255, 255]));
+        merger.bulk_push(&squares.iter().collect::<Vec<_>>());
+        let before = (**merger.get_canvas()).clone();
+        merger.remove_image_with_mode(1, mode).unwrap();
+
+        // The middle place holds the third image when compacting, and the background when leaving a hole.
+        let middle = match mode {
+            RemovalMode::Compact => squares[2][(0, 0)],
+            RemovalMode::Leave => background,
+        };
+        let expected_at = |index: u32| match (mode, index) {
+            (_, 1) => Some(middle),
+            (RemovalMode::Compact, 2) => Some(background),
+            _ => None,
+        };
+
+        let rectangles: Vec<_> = (0..3)
+            .map(|index| merger.coordinates_of_index(index))
+            .collect();
+        for (x, y, pixel) in merger.get_canvas().enumerate_pixels() {
+            let place = rectangles.iter().position(|&(left, top)| {
+                (left..left + IMAGE_WIDTH).contains(&x) && (top..top + IMAGE_HEIGHT).contains(&y)
+            });
+            match place.and_then(|index| expected_at(index as u32)) {
+                Some(expected) => assert_eq!(*pixel, expected, "at ({}, {})", x, y),
+                None => assert_eq!(*pixel, before[(x, y)], "at ({}, {})", x, y),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_merge_png_bytes() {
+    let test_square = generate_test_square();
+    let mut single: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 1);
+    single.push(&test_square);
+    let png = single.encode(image::ImageFormat::Png).unwrap();
+
+    let images = vec![png; 25];
+    let merged = merge_png_bytes(&images, IMAGES_PER_ROW).unwrap();
+    let canvas = image::load_from_memory_with_format(&merged, image::ImageFormat::Png)
+        .unwrap()
+        .to_rgba8();
+    assert_eq!(canvas, merge_images_slow(IMAGES_PER_ROW, 25).into_buffer());
+
+    assert!(matches!(
+        merge_png_bytes(&[vec![1, 2, 3]], IMAGES_PER_ROW),
+        Err(MergeError::Image(_))
+    ));
+    assert!(matches!(
+        merge_png_bytes(&[], IMAGES_PER_ROW),
+        Err(MergeError::NoImages)
+    ));
+}
+
+#[test]
+fn test_blend_premultiplied() {
+    // Straight red at about 75% opacity, premultiplied by its alpha.
+    let premultiplied =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([150, 0, 0, 200]));
+
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.push(&RgbaImageBuffer::new_from_pixel(
+        IMAGE_WIDTH,
+        IMAGE_HEIGHT,
+        Rgba([255; 4]),
+    ));
+    merger.push(&RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT));
+    merger
+        .replace_image_blended(0, &premultiplied, BlendMode::OverPremultiplied)
+        .unwrap();
+    merger
+        .replace_image_blended(1, &premultiplied, BlendMode::OverPremultiplied)
+        .unwrap();
+
+    // Over white, the red is not darkened by applying its alpha twice.
+    assert_eq!(merger.get_canvas()[(0, 0)], Rgba([205, 55, 55, 255]));
+    // Over a transparent place, the color is un-premultiplied back to straight alpha.
+    assert_eq!(
+        merger.get_canvas()[(IMAGE_WIDTH, 0)],
+        Rgba([191, 0, 0, 200])
+    );
+
+    let mut straight: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    straight.push(&RgbaImageBuffer::new_from_pixel(
+        IMAGE_WIDTH,
+        IMAGE_HEIGHT,
+        Rgba([255; 4]),
+    ));
+    straight
+        .replace_image_blended(0, &premultiplied, BlendMode::Over)
+        .unwrap();
+    assert!(straight.get_canvas()[(0, 0)][0] < 205);
+}
+
+#[test]
+fn test_push_raw() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    for _ in 0..15 {
+        merger
+            .push_raw(test_square.as_raw(), IMAGE_WIDTH, IMAGE_HEIGHT)
+            .unwrap();
+    }
+    assert_eq!(merger.get_canvas(), &merge_images_slow(IMAGES_PER_ROW, 15));
+
+    assert!(matches!(
+        merger.push_raw(&test_square.as_raw()[1..], IMAGE_WIDTH, IMAGE_HEIGHT),
+        Err(MergeError::BufferSizeMismatch {
+            expected: 40_000,
+            got: 39_999
+        })
+    ));
+    assert!(matches!(
+        merger.push_raw(test_square.as_raw(), IMAGE_WIDTH * 2, IMAGE_HEIGHT / 2),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_num_images(), 15);
+}
+
+#[test]
+fn test_rows() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES)
+            .with_padding(Padding {
+                x: PADDING_X,
+                y: PADDING_Y,
+            });
+    merger.bulk_push(&[&test_square; 25]);
+
+    let rows: Vec<_> = merger.rows().collect();
+    assert_eq!(rows.len(), 3);
+    let width = merger.get_canvas().width();
+    for (index, row) in rows.iter().enumerate() {
+        let y = index as u32 * (IMAGE_HEIGHT + PADDING_Y);
+        assert_eq!(row, &merger.crop_region(0, y, width, IMAGE_HEIGHT).unwrap());
+    }
+
+    let mut column_major: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_fill_order(FillOrder::ColumnMajor);
+    column_major.bulk_push(&[&test_square; 3]);
+    assert_eq!(column_major.rows().count(), 3);
+
+    let empty: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert_eq!(empty.rows().count(), 0);
+}
+
+#[test]
+fn test_memory_budget() {
+    use std::sync::{Arc, Mutex};
+
+    let width = IMAGE_WIDTH * IMAGES_PER_ROW + PADDING_X * (IMAGES_PER_ROW - 1);
+    let header = RgbaImageBuffer::new_from_pixel(width, 20, Rgba([0, 255, 0, 255]));
+    let squares: Vec<RgbaImageBuffer> = (0..25).map(generate_colored_square).collect();
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    let mut expected: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_padding(padding)
+            .with_header(Image::from(header.clone()));
+    expected.bulk_push(&squares.iter().collect::<Vec<_>>());
+
+    // The budget holds the header and two rows, so the third row has to flush the first two.
+    let budget = width as usize * (20 + 2 * IMAGE_HEIGHT + PADDING_Y) as usize * 4;
+    let flushed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&flushed);
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 50)
+            .with_padding(padding)
+            .with_header(header)
+            .with_memory_budget(budget, move |rows| sink.lock().unwrap().push(rows));
+    assert_eq!(
+        merger.get_canvas().height(),
+        20 + 2 * IMAGE_HEIGHT + PADDING_Y
+    );
+
+    merger.bulk_push(&squares[..15].iter().collect::<Vec<_>>());
+    for square in &squares[15..] {
+        merger.push(square);
+    }
+    assert_eq!(merger.get_num_flushed_rows(), 2);
+    assert_eq!(merger.get_num_images(), 5);
+    assert_eq!(merger.get_canvas().height(), 2 * IMAGE_HEIGHT + PADDING_Y);
+    assert!(merger.estimated_bytes() <= budget);
+
+    // The flushed rows, the padding below them, and the row left on the canvas make up the full canvas.
+    let flushed = flushed.lock().unwrap();
+    assert_eq!(flushed.len(), 1);
+    let flushed_height = 20 + 2 * IMAGE_HEIGHT + PADDING_Y;
+    assert_eq!(flushed[0].dimensions(), (width, flushed_height));
+    assert_eq!(
+        flushed[0],
+        expected.crop_region(0, 0, width, flushed_height).unwrap()
+    );
+    assert_eq!(
+        merger.crop_region(0, 0, width, IMAGE_HEIGHT).unwrap(),
+        expected
+            .crop_region(0, flushed_height + PADDING_Y, width, IMAGE_HEIGHT)
+            .unwrap()
+    );
+
+    // A bulk push that does not fit even after flushing is rejected.
+    assert!(matches!(
+        merger.try_bulk_push(&squares.iter().collect::<Vec<_>>()),
+        Err(MergeError::CanvasTooLarge { .. })
+    ));
+}
+
+#[test]
+#[should_panic]
+fn test_memory_budget_too_small() {
+    let _merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW)
+            .with_memory_budget(1, |_| {});
+}
+
+#[test]
+fn test_memory_budget_rejected_bulk_push() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    let flushes = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&flushes);
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2)
+        .with_memory_budget(
+            (IMAGE_WIDTH * 2 * IMAGE_HEIGHT * 2 * 4) as usize,
+            move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+    merger.bulk_push(&[&generate_colored_square(0); 4]);
+
+    // Pushing the images would flush both rows, but they are the wrong size, so nothing is flushed.
+    let wrong_size = RgbaImageBuffer::new(5, 5);
+    assert!(matches!(
+        merger.try_bulk_push(&[&wrong_size, &wrong_size]),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(flushes.load(Ordering::SeqCst), 0);
+    assert_eq!(merger.get_num_images(), 4);
+    assert_eq!(merger.get_num_flushed_rows(), 0);
+
+    merger
+        .try_bulk_push(&[&generate_colored_square(1); 2])
+        .unwrap();
+    assert_eq!(flushes.load(Ordering::SeqCst), 1);
+    assert_eq!(merger.get_num_images(), 2);
+}
+
+#[test]
+fn test_save_with_options() {
+    use image::codecs::png::{CompressionType, FilterType};
+
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&[&test_square; 15]);
+
+    let path = std::env::temp_dir().join("image_merger_test_save_with_options.png");
+    let options = EncodeOptions {
+        png_compression: CompressionType::Best,
+        png_filter: FilterType::Paeth,
+        ..EncodeOptions::default()
+    };
+    merger.save_with_options(&path, options).unwrap();
+    let saved = image::open(&path).unwrap().into_rgba8();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&saved, &**merger.get_canvas());
+
+    // A JPEG has no alpha channel, so an opaque Rgb canvas is saved instead.
+    let mut rgb_merger: GrowableMerger<Rgb<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    rgb_merger.push(&Image::from(
+        image::DynamicImage::from(test_square.into_buffer()).into_rgb8(),
+    ));
+    let path = std::env::temp_dir().join("image_merger_test_save_with_options.jpg");
+    let size_at = |quality: u8| {
+        let options = EncodeOptions {
+            jpeg_quality: quality,
+            ..EncodeOptions::default()
+        };
+        rgb_merger.save_with_options(&path, options).unwrap();
+        std::fs::metadata(&path).unwrap().len()
+    };
+    assert!(size_at(10) < size_at(100));
+    std::fs::remove_file(&path).unwrap();
+
+    let options = EncodeOptions {
+        jpeg_quality: 0,
+        ..EncodeOptions::default()
+    };
+    assert!(matches!(
+        rgb_merger.save_with_options(&path, options),
+        Err(image::ImageError::Parameter(_))
+    ));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_push_indexed() {
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    for index in 0..15 {
+        let square = generate_colored_square(index as u8);
+        assert_eq!(merger.push_indexed(&square).unwrap(), index);
+        assert_eq!(merger.get_image_at(index).unwrap(), square);
+    }
+
+    // After a sparse paste, the next index continues from the place after it.
+    merger.push_at(30, &generate_colored_square(30)).unwrap();
+    assert_eq!(
+        merger.push_indexed(&generate_colored_square(31)).unwrap(),
+        31
+    );
+
+    let wrong_size = RgbaImageBuffer::new(IMAGE_WIDTH + 1, IMAGE_HEIGHT);
+    assert!(matches!(
+        merger.push_indexed(&wrong_size),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_num_images(), 32);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_assert_matches_golden() {
+    let test_square = generate_test_square();
+    let mut merger: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    merger.bulk_push(&[&test_square; 5]);
+
+    let dir = std::env::temp_dir();
+    let golden = dir.join("image_merger_test_golden.png");
+    let actual = dir.join("image_merger_test_golden.actual.png");
+    merger.save(&golden).unwrap();
+    merger.assert_matches_golden(&golden).unwrap();
+    assert!(!actual.exists());
+
+    merger.fill_cell(2, Rgba([1, 2, 3, 4])).unwrap();
+    match merger.assert_matches_golden(&golden) {
+        Err(MergeError::GoldenMismatch {
+            first_diff,
+            differing_pixels,
+            actual: Some(path),
+        }) => {
+            assert_eq!(first_diff, (2 * IMAGE_WIDTH, 0));
+            assert_eq!(differing_pixels, (IMAGE_WIDTH * IMAGE_HEIGHT) as u64);
+            assert_eq!(path, actual);
+        }
+        other => panic!("Expected a golden mismatch, got {:?}", other),
+    }
+    let written = image::open(&actual).unwrap().into_rgba8();
+    assert_eq!(&written, &**merger.get_canvas());
+
+    // A canvas of a different size or color type is rejected before any pixels are compared.
+    merger.push(&test_square);
+    let mut wider: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW + 1);
+    wider.push(&test_square);
+    assert!(matches!(
+        wider.assert_matches_golden(&golden),
+        Err(MergeError::DimensionMismatch {
+            expected: (1000, 100),
+            got: (1100, 100)
+        })
+    ));
+    let rgb: GrowableMerger<Rgb<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW);
+    assert!(matches!(
+        rgb.assert_matches_golden(&golden),
+        Err(MergeError::Image(_))
+    ));
+
+    std::fs::remove_file(&golden).unwrap();
+    std::fs::remove_file(&actual).unwrap();
+}
+
+#[test]
+fn test_zero_images_per_row_is_rejected() {
+    assert!(matches!(
+        GrowableMerger::<Rgba<u8>>::try_new((IMAGE_WIDTH, IMAGE_HEIGHT), 0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        GrowableMerger::<Rgba<u8>>::with_canvas(
+            image::RgbaImage::new(0, 0),
+            (IMAGE_WIDTH, IMAGE_HEIGHT),
+            0
+        ),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let mut merger = GrowableMerger::<Rgba<u8>>::try_new((IMAGE_WIDTH, IMAGE_HEIGHT), 2).unwrap();
+    merger.bulk_push(&[&generate_test_square(); 3]);
+    assert!(matches!(
+        merger.set_images_per_row(0),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+    assert_eq!(merger.get_num_columns(), 2);
+}
+
+#[test]
+fn test_compact() {
+    let squares: Vec<RgbaImageBuffer> = (0..12).map(generate_colored_square).collect();
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    merger.bulk_push(&squares[..4].iter().collect::<Vec<_>>());
+
+    // Holes from a sparse paste and from removals that leave their place empty.
+    merger.push_at(8, &squares[8]).unwrap();
+    merger.push_at(6, &squares[6]).unwrap();
+    merger
+        .remove_image_with_mode(1, RemovalMode::Leave)
+        .unwrap();
+    assert!(!merger.is_cell_occupied(1));
+    assert!(!merger.is_cell_occupied(4));
+    assert!(merger.is_cell_occupied(6));
+
+    // Edits that shift images carry the holes along with them.
+    merger.remove_image(0).unwrap();
+    merger.insert_image(0, &squares[0]).unwrap();
+    merger.swap_images(1, 2).unwrap();
+    assert!(merger.is_cell_occupied(1));
+    assert!(!merger.is_cell_occupied(2));
+
+    assert_eq!(merger.compact(false).unwrap(), 4);
+    assert_eq!(merger.compact(false).unwrap(), 0);
+    assert_eq!(merger.get_num_images(), 5);
+    assert_eq!(merger.get_canvas().height(), 3 * IMAGE_HEIGHT);
+
+    let mut expected: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3);
+    expected.bulk_push(&[
+        &squares[0],
+        &squares[2],
+        &squares[3],
+        &squares[6],
+        &squares[8],
+    ]);
+    merger.shrink_to_fit().unwrap();
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+    assert!((0..5).all(|index| merger.is_cell_occupied(index)));
+
+    // Compacting can shrink the canvas in the same call.
+    merger.push_at(9, &squares[9]).unwrap();
+    assert_eq!(merger.get_canvas().height(), 4 * IMAGE_HEIGHT);
+    assert_eq!(merger.compact(true).unwrap(), 4);
+    assert_eq!(merger.get_canvas().height(), 2 * IMAGE_HEIGHT);
+    assert_eq!(merger.get_image_at(5).unwrap(), squares[9]);
+}
+
+#[test]
+fn test_fill_directions() {
+    use HorizontalDirection::*;
+    use VerticalDirection::*;
+
+    let squares: Vec<_> = (0..3).map(generate_colored_square).collect();
+    let cases = [
+        (
+            FillOrder::RowMajor,
+            LeftToRight,
+            TopToBottom,
+            [(0, 0), (1, 0), (0, 1)],
+        ),
+        (
+            FillOrder::RowMajor,
+            RightToLeft,
+            TopToBottom,
+            [(1, 0), (0, 0), (1, 1)],
+        ),
+        (
+            FillOrder::RowMajor,
+            LeftToRight,
+            BottomToTop,
+            [(0, 1), (1, 1), (0, 0)],
+        ),
+        (
+            FillOrder::RowMajor,
+            RightToLeft,
+            BottomToTop,
+            [(1, 1), (0, 1), (1, 0)],
+        ),
+        (
+            FillOrder::ColumnMajor,
+            RightToLeft,
+            TopToBottom,
+            [(1, 0), (1, 1), (0, 0)],
+        ),
+        (
+            FillOrder::ColumnMajor,
+            LeftToRight,
+            BottomToTop,
+            [(0, 1), (0, 0), (1, 1)],
+        ),
+    ];
+
+    for (fill_order, horizontal, vertical, places) in cases {
+        let mut merger: GrowableMerger<Rgba<u8>> =
+            GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2)
+                .with_fill_order(fill_order)
+                .with_fill_directions(horizontal, vertical);
+        assert_eq!(merger.get_fill_directions(), (horizontal, vertical));
+
+        // The images are pushed one by one, so the canvas grows while holding images.
+        for square in &squares {
+            merger.push(square);
+        }
+        assert_eq!(
+            merger.get_canvas().dimensions(),
+            (IMAGE_WIDTH * 2, IMAGE_HEIGHT * 2)
+        );
+
+        for (index, (column, row)) in places.into_iter().enumerate() {
+            let (x, y) = (column * IMAGE_WIDTH, row * IMAGE_HEIGHT);
+            assert_eq!(merger.coordinates_of_index(index as u32), (x, y));
+            assert_eq!(merger.index_at_pixel(x + 50, y + 50), Some(index as u32));
+            assert_eq!(merger.get_image_at(index as u32).unwrap(), squares[index]);
+        }
+    }
+
+    let mut expected: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2)
+            .with_fill_directions(RightToLeft, BottomToTop);
+    expected.bulk_push(&squares.iter().collect::<Vec<_>>());
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2)
+        .with_fill_directions(RightToLeft, BottomToTop);
+    for square in &squares {
+        merger.push(square);
+    }
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+
+    // Shrinking moves the images back down to the bottom of the canvas.
+    merger.pop();
+    merger.shrink_to_fit().unwrap();
+    assert_eq!(merger.coordinates_of_index(0), (IMAGE_WIDTH, 0));
+    assert_eq!(merger.get_image_at(1).unwrap(), squares[1]);
+}
+
+#[test]
+#[should_panic]
+fn test_fill_directions_after_push() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.push(&generate_colored_square(0));
+    let _ = merger.with_fill_directions(
+        HorizontalDirection::RightToLeft,
+        VerticalDirection::TopToBottom,
+    );
+}
+
+#[test]
+fn test_try_grow() {
+    let mut merger: GrowableMerger<Rgba<u8>> = GrowableMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2);
+    merger.bulk_push(&[&generate_colored_square(0); 3]);
+    merger.try_grow(0).unwrap();
+    merger.try_grow(2).unwrap();
+    assert_eq!(merger.get_canvas().height(), 4 * IMAGE_HEIGHT);
+    assert_eq!(merger.get_image_at(2).unwrap(), generate_colored_square(0));
+
+    let mut fixed: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::fixed_grid((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2);
+    assert!(matches!(
+        fixed.try_grow(1),
+        Err(MergeError::OutOfCapacity { capacity: 4 })
+    ));
+}
+
+#[test]
+fn test_try_grow_allocation_failed() {
+    // A canvas of 256 TiB can never be allocated, but its size can still be addressed, so the allocation itself fails.
+    let mut merger: GrowableMerger<Rgba<u16>> = GrowableMerger::new((8192, 1), 1);
+    let result = merger.try_grow(u32::MAX - 1);
+    assert!(matches!(
+        result,
+        Err(MergeError::AllocationFailed { bytes }) if bytes == 8192 * u32::MAX as usize * 8
+    ));
+
+    // The canvas is left as it was and can still be used.
+    assert_eq!(merger.get_canvas().dimensions(), (8192, 1));
+    merger
+        .try_push(&BufferedImage::new_from_pixel(8192, 1, Rgba([1; 4])))
+        .unwrap();
+    assert_eq!(merger.get_canvas().get_pixel(0, 0), &Rgba([1; 4]));
+}
@@ -38,7 +38,7 @@ fn merge_images_slow(
     padding_y: u32,
 ) -> RgbaImageBuffer {
     // Cieling division for total rows.
-    let total_rows = (total_images + images_per_row - 1) / images_per_row;
+    let total_rows = total_images.div_ceil(images_per_row);
 
     let test_square = generate_test_square();
 
@@ -257,3 +257,213 @@ fn test_resizable_bulk_known_size_merger() {
     merger.bulk_push_resized(&vec![&Image::from(test_square); TOTAL_IMAGES as usize]);
     assert!(merger.get_num_images() == TOTAL_IMAGES);
 }
+
+#[test]
+fn test_first_pushes_land_at_first_indices() {
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    );
+    assert_eq!(merger.last_pasted_index(), None);
+
+    let red = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]));
+    let blue = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([0, 0, 255, 255]));
+
+    merger.push(&red);
+    assert_eq!(merger.last_pasted_index(), Some(0));
+    merger.push(&blue);
+    assert_eq!(merger.last_pasted_index(), Some(1));
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+    assert_eq!(canvas.get_pixel(IMAGE_WIDTH, 0), &Rgba([0, 0, 255, 255]));
+}
+
+#[test]
+fn test_try_push_errors() {
+    let test_square = generate_test_square();
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 3, None);
+
+    let wrong_size = RgbaImageBuffer::new(IMAGE_WIDTH / 2, IMAGE_HEIGHT);
+    assert!(matches!(
+        merger.try_push(&wrong_size),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert!(matches!(
+        merger.try_bulk_push(&[&test_square; 5]),
+        Err(MergeError::OutOfCapacity { capacity: 4 })
+    ));
+    assert_eq!(merger.get_num_images(), 0);
+
+    merger.try_bulk_push(&[&test_square; 3]).unwrap();
+    merger.try_push(&test_square).unwrap();
+    assert!(matches!(
+        merger.try_push(&test_square),
+        Err(MergeError::OutOfCapacity { capacity: 4 })
+    ));
+    assert_eq!(merger.get_num_images(), 4);
+}
+
+#[test]
+#[should_panic(expected = "The canvas is full")]
+fn test_push_onto_full_canvas() {
+    let test_square = generate_test_square();
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 1, 1, None);
+    merger.push(&test_square);
+    merger.push(&test_square);
+}
+
+#[test]
+fn test_row_major_layout_matches_default() {
+    let test_square = generate_test_square();
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+    let layout = RowMajorLayout {
+        padding: Some(padding),
+    };
+    assert_eq!(
+        layout.canvas_size(TOTAL_IMAGES, (IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW),
+        (
+            IMAGE_WIDTH * IMAGES_PER_ROW + PADDING_X * (IMAGES_PER_ROW - 1),
+            IMAGE_HEIGHT * TOTAL_ROWS + PADDING_Y * (TOTAL_ROWS - 1)
+        )
+    );
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _, _> = KnownSizeMerger::with_layout(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        layout,
+    );
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+
+    let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES, PADDING_X, PADDING_Y);
+    assert_eq!(merger.get_canvas(), &slow_merge);
+}
+
+#[test]
+fn test_custom_layout() {
+    // Every other row is shifted right by half an image, like the bricks of a wall.
+    struct BrickLayout;
+
+    impl Layout for BrickLayout {
+        fn coordinates(
+            &self,
+            index: u32,
+            image_dimensions: (u32, u32),
+            per_row: u32,
+        ) -> (u32, u32) {
+            let row = index / per_row;
+            let offset = (row % 2) * image_dimensions.0 / 2;
+            (
+                (index % per_row) * image_dimensions.0 + offset,
+                row * image_dimensions.1,
+            )
+        }
+
+        fn canvas_size(
+            &self,
+            num_images: u32,
+            image_dimensions: (u32, u32),
+            per_row: u32,
+        ) -> (u32, u32) {
+            (
+                per_row * image_dimensions.0 + image_dimensions.0 / 2,
+                num_images.div_ceil(per_row) * image_dimensions.1,
+            )
+        }
+    }
+
+    let red = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]));
+    let mut merger: KnownSizeMerger<Rgba<u8>, _, _> =
+        KnownSizeMerger::with_layout((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, BrickLayout);
+    merger.bulk_push(&[&red; 3]);
+    merger.push(&red);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.dimensions(), (250, 200));
+    assert_eq!(canvas.get_pixel(249, 0), &Rgba([0, 0, 0, 0]));
+    assert_eq!(canvas.get_pixel(0, 100), &Rgba([0, 0, 0, 0]));
+    assert_eq!(canvas.get_pixel(249, 199), &Rgba([255, 0, 0, 255]));
+
+    merger.remove_image(3);
+    assert_eq!(merger.get_canvas().get_pixel(249, 199), &Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn test_zero_images_per_row_is_rejected() {
+    assert!(matches!(
+        KnownSizeMerger::<Rgba<u8>, _>::try_new((IMAGE_WIDTH, IMAGE_HEIGHT), 0, 10, None),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        KnownSizeMerger::<Rgba<u8>, _>::try_new((0, IMAGE_HEIGHT), 1, 10, None),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+
+    let container = vec![0u8; (IMAGE_WIDTH * IMAGE_HEIGHT * 4) as usize];
+    assert!(KnownSizeMerger::<Rgba<u8>, _>::new_from_raw(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        0,
+        1,
+        None,
+        container
+    )
+    .is_none());
+}
+
+#[test]
+#[should_panic(expected = "at least one image per row")]
+fn test_new_zero_images_per_row() {
+    let _: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 0, 10, None);
+}
+
+#[test]
+#[should_panic(expected = "at least one image per row")]
+fn test_with_layout_zero_images_per_row() {
+    let _: KnownSizeMerger<Rgba<u8>, _, _> = KnownSizeMerger::with_layout(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        0,
+        10,
+        RowMajorLayout { padding: None },
+    );
+}
+
+#[test]
+fn test_try_with_layout_zero_layout() {
+    let merger: Result<KnownSizeMerger<Rgba<u8>, _, _>, _> = KnownSizeMerger::try_with_layout(
+        (0, IMAGE_HEIGHT),
+        2,
+        10,
+        RowMajorLayout { padding: None },
+    );
+    assert!(matches!(merger, Err(MergeError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_row_major_layout_called_directly() {
+    let layout = RowMajorLayout {
+        padding: Some(Padding { x: 5, y: 5 }),
+    };
+
+    // Zero images per row lays the images out in a single column instead of dividing by zero.
+    assert_eq!(layout.coordinates(2, (10, 10), 0), (0, 30));
+    assert_eq!(layout.canvas_size(3, (10, 10), 0), (10, 40));
+
+    // Sizes too large for a u32 saturate instead of overflowing.
+    assert_eq!(
+        layout.canvas_size(u32::MAX, (u32::MAX, u32::MAX), 2),
+        (u32::MAX, u32::MAX)
+    );
+    assert_eq!(
+        layout.coordinates(u32::MAX, (u32::MAX, u32::MAX), 2),
+        (u32::MAX, u32::MAX)
+    );
+}
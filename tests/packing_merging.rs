@@ -0,0 +1,127 @@
+use image_merger::*;
+
+static ROW_WIDTH: u32 = 300;
+
+type RgbaImageBuffer = BufferedImage<Rgba<u8>>;
+
+fn generate_colored_image(width: u32, height: u32, index: u8) -> RgbaImageBuffer {
+    RgbaImageBuffer::new_from_pixel(width, height, Rgba([index, 0, 0, 255]))
+}
+
+#[test]
+fn test_images_wrap_to_new_rows() {
+    let images = [
+        generate_colored_image(120, 40, 1),
+        generate_colored_image(180, 60, 2),
+        generate_colored_image(100, 30, 3),
+        generate_colored_image(250, 50, 4),
+        generate_colored_image(50, 80, 5),
+    ];
+
+    let mut merger: PackingMerger<Rgba<u8>> = PackingMerger::new(ROW_WIDTH);
+    for image in &images {
+        merger.push(image);
+    }
+
+    // The first row is filled exactly, and each following row starts below the tallest image of the row above it.
+    let expected = [(0, 0), (120, 0), (0, 60), (0, 90), (250, 90)];
+    for (index, (x, y)) in expected.into_iter().enumerate() {
+        let (point, dimensions) = merger.get_placement(index as u32).unwrap();
+        assert_eq!(point, Point { x, y });
+        assert_eq!(dimensions, images[index].dimensions());
+    }
+    assert_eq!(merger.get_placement(5), None);
+    assert_eq!(merger.get_num_images(), 5);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.dimensions(), (ROW_WIDTH, 170));
+    for (index, (x, y)) in expected.into_iter().enumerate() {
+        let (width, height) = images[index].dimensions();
+        assert_eq!(canvas.get_pixel(x, y), &Rgba([index as u8 + 1, 0, 0, 255]));
+        assert_eq!(
+            canvas.get_pixel(x + width - 1, y + height - 1),
+            &Rgba([index as u8 + 1, 0, 0, 255])
+        );
+    }
+
+    // The space below the shorter images of a row is left empty.
+    assert_eq!(canvas.get_pixel(0, 40), &Rgba([0, 0, 0, 0]));
+    assert_eq!(canvas.get_pixel(100, 60), &Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn test_bulk_push_matches_push() {
+    let images: Vec<RgbaImageBuffer> = (0..20)
+        .map(|index| generate_colored_image(20 + index * 7, 10 + (index * 13) % 40, index as u8))
+        .collect();
+
+    let mut pushed: PackingMerger<Rgba<u8>> = PackingMerger::new(ROW_WIDTH);
+    for image in &images {
+        pushed.push(image);
+    }
+
+    let mut bulk_pushed: PackingMerger<Rgba<u8>> = PackingMerger::new(ROW_WIDTH);
+    let (first, rest) = images.split_at(3);
+    bulk_pushed.bulk_push(&first.iter().collect::<Vec<_>>());
+    bulk_pushed.bulk_push(&rest.iter().collect::<Vec<_>>());
+
+    assert_eq!(
+        bulk_pushed.get_canvas().as_raw(),
+        pushed.get_canvas().as_raw()
+    );
+    assert_eq!(
+        bulk_pushed.into_canvas().dimensions(),
+        pushed.into_canvas().dimensions()
+    );
+}
+
+#[test]
+fn test_image_too_wide() {
+    let mut merger: PackingMerger<Rgba<u8>> = PackingMerger::new(ROW_WIDTH);
+    merger.push(&generate_colored_image(100, 100, 1));
+
+    let wide = generate_colored_image(ROW_WIDTH + 1, 10, 2);
+    let narrow = generate_colored_image(10, 10, 3);
+    assert!(matches!(
+        merger.try_bulk_push(&[&narrow, &wide]),
+        Err(MergeError::ImageTooWide {
+            max_width: 300,
+            got: 301
+        })
+    ));
+
+    // Nothing was pushed, so the canvas is unchanged.
+    assert_eq!(merger.get_num_images(), 1);
+    assert_eq!(merger.get_canvas().dimensions(), (ROW_WIDTH, 100));
+}
+
+#[test]
+fn test_canvas_too_tall() {
+    let mut merger: PackingMerger<Rgba<u8>> = PackingMerger::new(ROW_WIDTH);
+    merger.push(&generate_colored_image(100, 100, 1));
+
+    // Every image fills a row of its own, so the rows add up to more than u32::MAX pixels long before anything is allocated.
+    let tall = generate_colored_image(ROW_WIDTH, 1 << 16, 2);
+    let images = vec![&tall; 1 << 16];
+    assert!(matches!(
+        merger.try_bulk_push(&images),
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: None
+        })
+    ));
+
+    // Nothing was placed, so the next image still goes next to the first one.
+    assert_eq!(merger.get_num_images(), 1);
+    assert_eq!(merger.get_canvas().dimensions(), (ROW_WIDTH, 100));
+    merger.push(&generate_colored_image(100, 100, 3));
+    assert_eq!(
+        merger.get_placement(1),
+        Some((Point { x: 100, y: 0 }, (100, 100)))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_new_zero_row_width() {
+    let _: PackingMerger<Rgba<u8>> = PackingMerger::new(0);
+}
@@ -0,0 +1,85 @@
+use image_merger::*;
+
+static IMAGES_PER_ROW: u32 = 10;
+static TOTAL_IMAGES: u32 = 95;
+static IMAGE_WIDTH: u32 = 100;
+static IMAGE_HEIGHT: u32 = 100;
+
+type RgbaImageBuffer = BufferedImage<Rgba<u8>>;
+
+fn generate_colored_square(index: u8) -> RgbaImageBuffer {
+    RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([index, 0, 0, 255]))
+}
+
+#[test]
+fn test_streaming_png_matches_growable() {
+    let path = std::env::temp_dir().join("image_merger_test_streaming.png");
+    let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+
+    let mut streaming: StreamingPngMerger<Rgba<u8>, _> = StreamingPngMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        file,
+    )
+    .unwrap();
+    let mut growable: GrowableMerger<Rgba<u8>> =
+        GrowableMerger::with_capacity((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES);
+    for index in 0..TOTAL_IMAGES - 3 {
+        let square = generate_colored_square(index as u8);
+        streaming.push(&square).unwrap();
+        growable.push(&square);
+    }
+    assert!(matches!(
+        streaming.push(&RgbaImageBuffer::new(1, 1)),
+        Err(MergeError::DimensionMismatch { .. })
+    ));
+    assert_eq!(streaming.get_num_images(), TOTAL_IMAGES - 3);
+    assert_eq!(
+        streaming.get_image_dimensions(),
+        (IMAGE_WIDTH, IMAGE_HEIGHT)
+    );
+    streaming.finish().unwrap();
+
+    let decoded = image::open(&path).unwrap().into_rgba8();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&decoded, &**growable.get_canvas());
+}
+
+#[test]
+fn test_streaming_png_errors() {
+    let path = std::env::temp_dir().join("image_merger_test_streaming_errors.png");
+
+    let file = std::fs::File::create(&path).unwrap();
+    let mut merger: StreamingPngMerger<Luma<u16>, _> =
+        StreamingPngMerger::new((2, 2), 1, 1, file).unwrap();
+    merger.push(&BufferedImage::new(2, 2)).unwrap();
+    assert!(matches!(
+        merger.push(&BufferedImage::new(2, 2)),
+        Err(MergeError::OutOfCapacity { capacity: 1 })
+    ));
+    merger.finish().unwrap();
+
+    let file = std::fs::File::create(&path).unwrap();
+    assert!(matches!(
+        StreamingPngMerger::<Rgb<f32>, _>::new((2, 2), 1, 1, file),
+        Err(MergeError::Image(_))
+    ));
+
+    // The width of the canvas does not fit in a u32.
+    let file = std::fs::File::create(&path).unwrap();
+    assert!(matches!(
+        StreamingPngMerger::<Rgb<u8>, _>::new((70_000, 1), 70_000, 70_000, file),
+        Err(MergeError::CanvasTooLarge {
+            max_dimensions: None
+        })
+    ));
+
+    // A layout with no images per row is rejected up front, instead of dividing by zero.
+    let file = std::fs::File::create(&path).unwrap();
+    assert!(matches!(
+        StreamingPngMerger::<Rgb<u8>, _>::new((2, 2), 0, 1, file),
+        Err(MergeError::InvalidConfiguration(_))
+    ));
+    std::fs::remove_file(&path).unwrap();
+}